@@ -2,14 +2,21 @@
 
 use arc::{
     args,
-    file::{Grid as FileGrid, Load, Save, Verse as FileVerse},
+    bio::Response,
+    data::Table,
+    file::{
+        Fitting, Grid as FileGrid, Heating, Kinetics, Load, Save, Stochastic, Verse as FileVerse,
+    },
     report,
     util::{banner, exec, io_dirs},
 };
 use attr::form;
 use colog;
 use log::info;
-use std::path::{Path, PathBuf};
+use std::{
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+};
 
 #[form]
 struct Parameters {
@@ -17,6 +24,11 @@ struct Parameters {
     num_phot: f64,
     verse: FileVerse,
     grid: FileGrid,
+    kin: Option<Kinetics>,
+    resp: Option<Response>,
+    ssa: Option<Stochastic>,
+    fit: Option<Fitting>,
+    heat: Option<Heating>,
 }
 
 fn main() {
@@ -35,9 +47,12 @@ fn main() {
 
     banner::section("Loading");
     let verse = params.verse.form(&in_dir);
+    if let Some(resp) = &params.resp {
+        resp.check(verse.specs());
+    }
 
     banner::section("Building");
-    let grid = params.grid.form(&verse);
+    let mut grid = params.grid.form(&in_dir, &verse);
 
     banner::section("Overview");
     info!("Universe contents:\n{}", verse);
@@ -73,10 +88,11 @@ fn main() {
         map.map(|x| **x)
             .save(&out_dir.join(format!("{}_map.nc", name)));
     }
+    drop(specs_refs);
     boundaries.save(&out_dir.join("boundaries.nc"));
 
     banner::section("Simulation");
-    let light_map = arc::sim::mcrt::run(
+    let mut light_map = arc::sim::mcrt::run(
         &arc::dom::Name::new("first"),
         params.num_phot as u64,
         &verse,
@@ -86,14 +102,120 @@ fn main() {
     banner::section("Output 2");
     light_map.save(&out_dir);
     let mut tumour_dosage = 0.0;
-    let mat_names = grid.mat_names();
-    for (rec, name) in light_map.recs().iter().zip(mat_names.iter()) {
-        if name.str() == "tumour" {
+    for (rec, cell) in light_map.recs().iter().zip(grid.cells().iter()) {
+        if cell.mat().str() == "tumour" {
             tumour_dosage += rec.absorptions();
         }
     }
     report!(tumour_dosage);
 
+    if let Some(heat) = &params.heat {
+        banner::section("Heating");
+        let table = arc::sim::heat::run(&verse, &mut grid, &light_map, heat, &out_dir);
+        table.save(&out_dir.join("heat.csv"));
+
+        let coagulated = arc::sim::heat::coagulated(&verse, &grid);
+        if coagulated > 0 {
+            info!(
+                "Re-simulating light through {} coagulated cells",
+                coagulated
+            );
+            light_map = arc::sim::mcrt::run(
+                &arc::dom::Name::new("first"),
+                params.num_phot as u64,
+                &verse,
+                &grid,
+            );
+            let coagulated_dir = out_dir.join("coagulated");
+            create_dir_all(&coagulated_dir).expect("Unable to create output directory.");
+            light_map.save(&coagulated_dir);
+        }
+    }
+
+    if let Some(kin) = &params.kin {
+        if let Some(steady) = kin.steady() {
+            banner::section("Steady state");
+            arc::sim::kin::illuminate(&mut grid, &light_map);
+            let table = arc::sim::steady::run(&verse, &mut grid, kin, steady);
+            table.save(&out_dir.join("steady.csv"));
+            for (name, map) in grid.specs_refs(verse.specs()).map() {
+                map.map(|x| **x)
+                    .save(&out_dir.join(format!("{}_steady.nc", name)));
+            }
+        } else {
+            banner::section("Kinetics");
+            let table = if kin.cycles().is_some() {
+                let (light_map, table) = arc::sim::pdt::run(
+                    &arc::dom::Name::new("first"),
+                    params.num_phot as u64,
+                    &verse,
+                    &mut grid,
+                    kin,
+                    &out_dir,
+                );
+                let cycled_dir = out_dir.join("cycled");
+                create_dir_all(&cycled_dir).expect("Unable to create output directory.");
+                light_map.save(&cycled_dir);
+                table
+            } else {
+                arc::sim::kin::illuminate(&mut grid, &light_map);
+                arc::sim::kin::run(&verse, &mut grid, kin, &out_dir)
+            };
+            table.save(&out_dir.join("kinetics.csv"));
+        }
+    }
+
+    if let Some(ssa) = &params.ssa {
+        banner::section("Stochastic kinetics");
+        info!("{} ensemble of {} cells", ssa.method(), ssa.runs());
+        let table = arc::sim::ssa::run(&verse, ssa);
+        table.save(&out_dir.join("stochastic.csv"));
+    }
+
+    if let Some(fit) = &params.fit {
+        banner::section("Fitting");
+        let (summary, tables) = arc::sim::fit::run(&in_dir, &verse, fit);
+        for heading in summary.headings() {
+            let col = summary.column(heading).expect("Missing column.");
+            println!(
+                "{:<32}\t{} -> {} +/- {}\t[{}, {}]",
+                format!("{}:", heading),
+                col[0],
+                col[1],
+                col[2],
+                col[3],
+                col[4]
+            );
+        }
+        summary.save(&out_dir.join("fit.csv"));
+        for (file, table) in fit.data().iter().zip(&tables) {
+            let stem = Path::new(file)
+                .file_stem()
+                .expect("Invalid data file name.")
+                .to_string_lossy();
+            table.save(&out_dir.join(format!("{}_fit.csv", stem)));
+        }
+    }
+
+    if let Some(resp) = &params.resp {
+        banner::section("Response");
+        info!("Survival model: {}", resp);
+        let survival = resp.survival(&grid, verse.specs());
+        survival.save(&out_dir.join("survival.nc"));
+
+        let mat_survival = Response::mat_survival(&grid, &survival);
+        for (name, s) in &mat_survival {
+            println!("{:<32}\t{}", format!("{}:", name), s);
+        }
+        if let Some(tumour_survival) = mat_survival.get(&arc::dom::Name::new("tumour")) {
+            report!(tumour_survival, "tumour surviving fraction");
+        }
+
+        let mut table = Table::new(mat_survival.keys().map(ToString::to_string).collect());
+        table.push(mat_survival.values().cloned().collect());
+        table.save(&out_dir.join("survival.csv"));
+    }
+
     banner::section("Finished");
 }
 
@@ -0,0 +1,90 @@
+//! Mie scattering optical properties generator.
+
+use arc::{
+    args,
+    data::Table,
+    file::{Load, Save},
+    phys::Particles,
+    report,
+    util::{banner, exec, io_dirs},
+};
+use attr::form;
+use colog;
+use log::info;
+use std::path::{Path, PathBuf};
+
+#[form]
+struct Parameters {
+    particles: Particles,
+    wavelengths: Vec<f64>,
+    num_angles: usize,
+}
+
+fn main() {
+    colog::init();
+    banner::title(&exec::name());
+
+    banner::section("initialisation");
+    let (in_dir, out_dir, params_path) = initialisation();
+    report!(in_dir.display(), "input directory");
+    report!(out_dir.display(), "output directory");
+    report!(params_path.display(), "parameters path");
+
+    banner::section("Prelude");
+    let params = prelude(&params_path);
+    info!("loaded parameters file");
+    report!(params.particles, "particles");
+
+    banner::section("Simulation");
+    for w in &params.wavelengths {
+        info!("({}nm): {}", w * 1.0e9, params.particles.env(*w));
+    }
+    let optics = params.particles.optics(&params.wavelengths);
+
+    banner::section("Output");
+    optics.save(&out_dir.join("optics.json"));
+
+    let mus: Vec<f64> = (0..params.num_angles)
+        .map(|i| (i as f64 / (params.num_angles - 1) as f64 * std::f64::consts::PI).cos())
+        .collect();
+    let mut headings = vec!["mu".to_string()];
+    for w in &params.wavelengths {
+        headings.push(format!("{}nm", w * 1.0e9));
+    }
+    let mut table = Table::new(headings);
+    let phases: Vec<Vec<f64>> = params
+        .wavelengths
+        .iter()
+        .map(|w| params.particles.phase(*w, &mus))
+        .collect();
+    for (i, mu) in mus.iter().enumerate() {
+        let mut row = vec![*mu];
+        for phase in &phases {
+            row.push(phase[i]);
+        }
+        table.push(row);
+    }
+    table.save(&out_dir.join("phase.csv"));
+
+    banner::section("Finished");
+}
+
+fn initialisation() -> (PathBuf, PathBuf, PathBuf) {
+    args!(_bin_path: String;
+        params_name: String);
+
+    let (in_dir, out_dir) = io_dirs(None, None);
+    let params_path = &in_dir.join(params_name);
+
+    (in_dir, out_dir, params_path.to_path_buf())
+}
+
+fn prelude(params_path: &Path) -> Parameters {
+    let params = Parameters::load(&params_path);
+    assert!(
+        params.num_angles >= 2,
+        "At least two phase function angles are required."
+    );
+
+    params
+}
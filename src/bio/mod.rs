@@ -0,0 +1,5 @@
+//! Biology module.
+
+pub mod resp;
+
+pub use self::resp::*;
@@ -0,0 +1,6 @@
+//! Response sub-module.
+
+pub mod model;
+pub mod response;
+
+pub use self::{model::*, response::*};
@@ -0,0 +1,49 @@
+//! Survival model implementation.
+
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Cell survival models converting an accumulated damage dose into a surviving fraction.
+#[json]
+pub enum Model {
+    /// All cells die once the dose reaches the threshold. (threshold)
+    Threshold(f64),
+    /// Linear-quadratic survival, S = exp(-(alpha D + beta D^2)). (alpha, beta)
+    LinearQuadratic(f64, f64),
+}
+
+impl Model {
+    /// Calculate the surviving fraction for a given dose.
+    #[inline]
+    #[must_use]
+    pub fn survival(&self, dose: f64) -> f64 {
+        assert!(dose >= 0.0);
+
+        match self {
+            Self::Threshold(threshold) => {
+                assert!(*threshold > 0.0);
+                if dose < *threshold {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::LinearQuadratic(alpha, beta) => {
+                assert!(*alpha >= 0.0);
+                assert!(*beta >= 0.0);
+                (-dose * beta.mul_add(dose, *alpha)).exp()
+            }
+        }
+    }
+}
+
+impl Display for Model {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Threshold(threshold) => write!(fmt, "Threshold ({})", threshold),
+            Self::LinearQuadratic(alpha, beta) => {
+                write!(fmt, "Linear-quadratic ({}, {})", alpha, beta)
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Response implementation.
+
+use crate::{
+    access,
+    bio::Model,
+    chem::Species,
+    dom::{Name, Regular, Set},
+};
+use attr::json;
+use ndarray::Array3;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Biological response to an accumulated damage species.
+#[json]
+pub struct Response {
+    /// Species whose concentration is the accumulated damage dose.
+    spec: Name,
+    /// Survival model.
+    model: Model,
+}
+
+impl Response {
+    access!(spec, Name);
+    access!(model, Model);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub const fn new(spec: Name, model: Model) -> Self {
+        Self { spec, model }
+    }
+
+    /// Check that the dose species is within the species set.
+    /// Panics with a clear message if no reaction uses the dose species, as it would not have been loaded.
+    #[inline]
+    pub fn check(&self, specs: &Set<Species>) {
+        assert!(
+            specs.map().contains_key(&self.spec),
+            "Response dose species {} is not used by any reaction.",
+            self.spec
+        );
+    }
+
+    /// Create a map of the surviving fraction of cells within each grid cell.
+    #[inline]
+    #[must_use]
+    pub fn survival(&self, grid: &Regular, specs: &Set<Species>) -> Array3<f64> {
+        grid.spec_refs(&self.spec, specs)
+            .map(|dose| self.model.survival(dose.max(0.0)))
+    }
+
+    /// Determine the mean surviving fraction within each material of the grid.
+    #[inline]
+    #[must_use]
+    pub fn mat_survival(grid: &Regular, survival: &Array3<f64>) -> BTreeMap<Name, f64> {
+        let mut totals: BTreeMap<Name, (f64, usize)> = BTreeMap::new();
+        for (name, s) in grid.mat_names().iter().zip(survival.iter()) {
+            let total = totals.entry((*name).clone()).or_insert((0.0, 0));
+            total.0 += s;
+            total.1 += 1;
+        }
+
+        totals
+            .into_iter()
+            .map(|(name, (sum, count))| (name, sum / count as f64))
+            .collect()
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "{} dose\t{}", self.spec, self.model)
+    }
+}
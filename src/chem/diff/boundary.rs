@@ -0,0 +1,85 @@
+//! Boundary implementation.
+
+use crate::{access, dom::Name};
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Species boundary conditions.
+#[json]
+pub enum Boundary {
+    /// Fixed concentration.
+    Fixed(f64),
+    /// Fixed inward flux. [conc m s^-1]
+    Flux(f64),
+    /// Zero flux.
+    Closed,
+}
+
+impl Display for Boundary {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Fixed(conc) => write!(fmt, "Fixed ({})", conc),
+            Self::Flux(flux) => write!(fmt, "Flux ({})", flux),
+            Self::Closed => write!(fmt, "Closed"),
+        }
+    }
+}
+
+/// Grid faces.
+#[json]
+#[derive(Clone, PartialEq)]
+pub enum Face {
+    /// Minimum x face.
+    XMin,
+    /// Maximum x face.
+    XMax,
+    /// Minimum y face.
+    YMin,
+    /// Maximum y face.
+    YMax,
+    /// Minimum z face.
+    ZMin,
+    /// Maximum z face.
+    ZMax,
+}
+
+impl Face {
+    /// Get the dimension index and whether the face is at the maximum of that dimension.
+    #[inline]
+    #[must_use]
+    pub fn dim_max(&self) -> (usize, bool) {
+        match self {
+            Self::XMin => (0, false),
+            Self::XMax => (0, true),
+            Self::YMin => (1, false),
+            Self::YMax => (1, true),
+            Self::ZMin => (2, false),
+            Self::ZMax => (2, true),
+        }
+    }
+}
+
+/// Boundary condition of a single species on a single grid face.
+/// Faces without a condition are closed.
+#[json]
+pub struct FaceBound {
+    /// Grid face.
+    face: Face,
+    /// Species.
+    spec: Name,
+    /// Condition.
+    bound: Boundary,
+}
+
+impl FaceBound {
+    access!(face, Face);
+    access!(spec, Name);
+    access!(bound, Boundary);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub const fn new(face: Face, spec: Name, bound: Boundary) -> Self {
+        Self { face, spec, bound }
+    }
+}
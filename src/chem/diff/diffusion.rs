@@ -0,0 +1,432 @@
+//! Diffusion implementation.
+
+use crate::{
+    access,
+    chem::{Boundary, Cream, FaceBound, Pharmacokinetics, Plasma, Topical},
+    dom::{index_of_name, Name, Regular},
+    uni::Verse,
+};
+use nalgebra::Vector3;
+use ndarray::{Array3, Array4};
+use std::collections::BTreeMap;
+
+/// Fraction of the explicit stability limit used for each diffusion sub-step.
+const CFL_SAFETY: f64 = 0.9;
+
+/// Finite-volume diffusion operator over a regular grid.
+/// Fluxes between cells use the harmonic mean of their diffusion coefficients, unless an interface barrier separates them,
+/// so impermeable cells, and grid faces without a boundary condition, receive no flux.
+pub struct Diffusion {
+    /// Diffusion coefficients of each species within each cell. [m^2 s^-1]
+    coeffs: Array4<f64>,
+    /// Cell widths. [m]
+    cell_size: Vector3<f64>,
+    /// Material index of each cell.
+    mat_indices: Array3<usize>,
+    /// Partition coefficient and interface resistance of each species across material interfaces. (in, out) -> [(K, 1 / P)]
+    barriers: BTreeMap<(usize, usize), Vec<(f64, f64)>>,
+    /// Boundary and perfusion source terms of each species within each cell. [conc s^-1]
+    sources: Array4<f64>,
+    /// Boundary and perfusion sink coefficients of each species within each cell. [s^-1]
+    sinks: Array4<f64>,
+    /// Species concentrations held fixed within cells. ([xi, yi, zi, species], concentration)
+    held: Vec<([usize; 4], f64)>,
+    /// Topically applied creams.
+    creams: Vec<Cream>,
+    /// Optional systemic plasma supply of vascular cells.
+    plasma: Option<Plasma>,
+    /// Largest stable explicit time step. [s]
+    max_dt: f64,
+}
+
+impl Diffusion {
+    access!(coeffs, Array4<f64>);
+    access!(cell_size, Vector3<f64>);
+    access!(mat_indices, Array3<usize>);
+    access!(barriers, BTreeMap<(usize, usize), Vec<(f64, f64)>>);
+    access!(sources, Array4<f64>);
+    access!(sinks, Array4<f64>);
+    access!(held, Vec<([usize; 4], f64)>);
+    access!(creams, Vec<Cream>);
+    access!(plasma, Option<Plasma>);
+    access!(max_dt, f64);
+
+    /// Construct a new instance.
+    /// Materials without a viscosity are impermeable and species without a radius are non-diffusive.
+    /// Without a temperature no species diffuse, but boundary fluxes, perfusion and held concentrations still apply.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        verse: &Verse,
+        grid: &Regular,
+        temp: Option<f64>,
+        faces: &[FaceBound],
+        topicals: &[Topical],
+        pk: Option<&Pharmacokinetics>,
+    ) -> Self {
+        let res = grid.res();
+        let [nx, ny, nz] = res;
+        let specs: Vec<_> = verse.specs().map().iter().collect();
+        let shape = [nx, ny, nz, specs.len()];
+
+        let mut cell_size = grid.bound().widths();
+        for (w, n) in cell_size.iter_mut().zip(res.iter()) {
+            *w /= *n as f64;
+        }
+
+        let mats = grid.cells().map(|cell| {
+            verse
+                .mats()
+                .map()
+                .get(cell.mat())
+                .expect("Invalid material name.")
+        });
+
+        let mut coeffs = Array4::zeros(shape);
+        let mut held = Vec::new();
+        for ((xi, yi, zi), mat) in mats.indexed_iter() {
+            for (si, (name, spec)) in specs.iter().enumerate() {
+                let bound = mat.bounds().as_ref().and_then(|bounds| bounds.get(name));
+
+                if let Some(Boundary::Fixed(conc)) = bound {
+                    held.push(([xi, yi, zi, si], *conc));
+                }
+
+                if let (Some(temp), Some(visc), false) =
+                    (temp, mat.visc(), matches!(bound, Some(Boundary::Closed)))
+                {
+                    *coeffs
+                        .get_mut([xi, yi, zi, si])
+                        .expect("Invalid coefficient index.") =
+                        spec.diff_coeff(*visc, temp).unwrap_or(0.0);
+                }
+            }
+        }
+
+        let mut sources = Array4::zeros(shape);
+        let mut sinks = Array4::zeros(shape);
+
+        for face_bound in faces {
+            let si = index_of_name(face_bound.spec(), verse.specs());
+            let (dim, max) = face_bound.face().dim_max();
+            let dx = *cell_size.get(dim).expect("Invalid dimension.");
+            let layer = if max {
+                res.get(dim).expect("Invalid dimension.") - 1
+            } else {
+                0
+            };
+
+            for ((xi, yi, zi, s), d) in coeffs.indexed_iter() {
+                if s != si || [xi, yi, zi].get(dim) != Some(&layer) {
+                    continue;
+                }
+
+                let index = [xi, yi, zi, s];
+                match face_bound.bound() {
+                    Boundary::Fixed(conc) => {
+                        let coeff = 2.0 * d / dx.powi(2);
+                        *sinks.get_mut(index).expect("Invalid cell index.") += coeff;
+                        *sources.get_mut(index).expect("Invalid cell index.") += coeff * conc;
+                    }
+                    Boundary::Flux(flux) => {
+                        *sources.get_mut(index).expect("Invalid cell index.") += flux / dx
+                    }
+                    Boundary::Closed => {}
+                }
+            }
+        }
+
+        for ((xi, yi, zi), mat) in mats.indexed_iter() {
+            if let Some(perfusion) = mat.perfusion() {
+                for (name, conc) in perfusion.arterial() {
+                    let index = [xi, yi, zi, index_of_name(name, verse.specs())];
+                    *sinks.get_mut(index).expect("Invalid cell index.") += perfusion.rate();
+                    *sources.get_mut(index).expect("Invalid cell index.") +=
+                        perfusion.rate() * conc;
+                }
+            }
+
+            if let Some(bounds) = mat.bounds() {
+                for (si, (name, _spec)) in specs.iter().enumerate() {
+                    if let Some(Boundary::Flux(flux)) = bounds.get(name) {
+                        let cell = grid.cells().get([xi, yi, zi]).expect("Invalid cell index.");
+                        for (next, dx) in neighbours([xi, yi, zi], res, &cell_size) {
+                            let [nxi, nyi, nzi] = next;
+                            let next_index = [nxi, nyi, nzi, si];
+                            if grid.cells().get(next).expect("Invalid cell index.").mat()
+                                != cell.mat()
+                                && *coeffs.get(next_index).expect("Invalid cell index.") > 0.0
+                            {
+                                *sources.get_mut(next_index).expect("Invalid cell index.") +=
+                                    flux / dx;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mat_index = |name| {
+            verse
+                .mats()
+                .map()
+                .keys()
+                .position(|key| key == name)
+                .expect("Invalid material name.")
+        };
+        let mut barriers = BTreeMap::new();
+        for inter in verse.inters().map().values() {
+            if inter.partition().is_none() && inter.perm().is_none() {
+                continue;
+            }
+
+            let barrier = specs
+                .iter()
+                .map(|(name, _spec)| {
+                    let k = inter
+                        .partition()
+                        .as_ref()
+                        .and_then(|partition| partition.get(name))
+                        .map_or(1.0, |k| *k);
+                    let res = inter
+                        .perm()
+                        .as_ref()
+                        .and_then(|perm| perm.get(name))
+                        .map_or(0.0, |p| 1.0 / p);
+                    assert!(k > 0.0);
+                    (k, res)
+                })
+                .collect();
+            barriers.insert(
+                (mat_index(inter.in_mat()), mat_index(inter.out_mat())),
+                barrier,
+            );
+        }
+
+        let mut diff = Self {
+            coeffs,
+            cell_size,
+            mat_indices: grid.cells().map(|cell| mat_index(cell.mat())),
+            barriers,
+            sources,
+            sinks,
+            held,
+            creams: topicals
+                .iter()
+                .map(|topical| Cream::new(topical, verse, grid, &cell_size))
+                .collect(),
+            plasma: pk.map(|pk| Plasma::new(pk, verse, grid)),
+            max_dt: std::f64::INFINITY,
+        };
+        diff.max_dt = diff.stable_dt();
+
+        diff
+    }
+
+    /// Determine the largest stable explicit time step from the largest diagonal rate coefficient. [s]
+    fn stable_dt(&self) -> f64 {
+        let max_diag = self.diagonal().iter().cloned().fold(0.0, f64::max);
+
+        if max_diag > 0.0 {
+            1.0 / max_diag
+        } else {
+            std::f64::INFINITY
+        }
+    }
+
+    /// Calculate the diagonal of the negated rate Jacobian, the rate at which each concentration decays. [s^-1]
+    #[inline]
+    #[must_use]
+    pub fn diagonal(&self) -> Array4<f64> {
+        let (nx, ny, nz, _ns) = self.coeffs.dim();
+        let res = [nx, ny, nz];
+
+        let mut diag = self.sinks.clone();
+        for ((xi, yi, zi, si), d) in diag.indexed_iter_mut() {
+            for (next, dx) in neighbours([xi, yi, zi], res, &self.cell_size) {
+                *d += self.face([xi, yi, zi], next, si, dx).1 / dx;
+            }
+        }
+
+        diag
+    }
+
+    /// Determine the face conductances between a cell and its neighbour for a species. [m s^-1]
+    /// The flux into the cell is the first multiplied by the neighbour concentration, less the second multiplied by the cell concentration.
+    /// Within a material, or across an interface without a barrier, the conductance is the harmonic mean coefficient over the separation.
+    /// Otherwise the half-cell resistances act in series with the interface resistance, driven by the partitioned concentration difference.
+    #[inline]
+    #[must_use]
+    pub fn face(&self, index: [usize; 3], next: [usize; 3], si: usize, dx: f64) -> (f64, f64) {
+        let [xi, yi, zi] = index;
+        let [nxi, nyi, nzi] = next;
+        let d = *self
+            .coeffs
+            .get([xi, yi, zi, si])
+            .expect("Invalid cell index.");
+        let d_next = *self
+            .coeffs
+            .get([nxi, nyi, nzi, si])
+            .expect("Invalid cell index.");
+        if d <= 0.0 || d_next <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let mat = *self.mat_indices.get(index).expect("Invalid cell index.");
+        let mat_next = *self.mat_indices.get(next).expect("Invalid cell index.");
+        if mat != mat_next {
+            if let Some(barrier) = self.barriers.get(&(mat, mat_next)) {
+                let (k, res) = *barrier.get(si).expect("Invalid species index.");
+                let g = 1.0 / ((dx / (2.0 * d_next)) + res + (dx / (2.0 * d * k)));
+                return (g, g / k);
+            }
+            if let Some(barrier) = self.barriers.get(&(mat_next, mat)) {
+                let (k, res) = *barrier.get(si).expect("Invalid species index.");
+                let g = 1.0 / ((dx / (2.0 * d)) + res + (dx / (2.0 * d_next * k)));
+                return (g / k, g);
+            }
+        }
+
+        let g = 2.0 * d * d_next / ((d + d_next) * dx);
+        (g, g)
+    }
+
+    /// Calculate the rate of change of the concentrations due to diffusion and boundary conditions.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self, concs: &Array4<f64>) -> Array4<f64> {
+        assert!(concs.shape() == self.coeffs.shape());
+
+        let (nx, ny, nz, _ns) = concs.dim();
+        let res = [nx, ny, nz];
+        let mut rate = &self.sources - &(&self.sinks * concs);
+
+        for ((xi, yi, zi, si), r) in rate.indexed_iter_mut() {
+            if *self
+                .coeffs
+                .get([xi, yi, zi, si])
+                .expect("Invalid cell index.")
+                <= 0.0
+            {
+                continue;
+            }
+
+            let conc = concs.get([xi, yi, zi, si]).expect("Invalid cell index.");
+            for (next, dx) in neighbours([xi, yi, zi], res, &self.cell_size) {
+                let (g_next, g) = self.face([xi, yi, zi], next, si, dx);
+                let [nxi, nyi, nzi] = next;
+                let conc_next = concs.get([nxi, nyi, nzi, si]).expect("Invalid cell index.");
+                *r += ((g_next * conc_next) - (g * conc)) / dx;
+            }
+        }
+
+        rate
+    }
+
+    /// Set the held concentrations.
+    #[inline]
+    pub fn hold(&self, concs: &mut Array4<f64>) {
+        for (index, conc) in &self.held {
+            *concs.get_mut(*index).expect("Invalid cell index.") = *conc;
+        }
+    }
+
+    /// Evolve the concentrations by diffusion over a given duration using stable explicit sub-steps.
+    /// Creams are released, and vascular cells exchange with the plasma, at the start of each sub-step.
+    /// Returns the number of sub-steps taken.
+    #[inline]
+    pub fn evolve(&mut self, concs: &mut Array4<f64>, duration: f64) -> usize {
+        assert!(duration >= 0.0);
+
+        if duration <= 0.0 {
+            self.hold(concs);
+            return 0;
+        }
+
+        let steps = (duration / (CFL_SAFETY * self.max_dt)).ceil().max(1.0) as usize;
+        let dt = duration / steps as f64;
+        for _ in 0..steps {
+            for cream in &mut self.creams {
+                cream.release(concs, dt);
+            }
+            if let Some(plasma) = &mut self.plasma {
+                plasma.exchange(concs, dt);
+            }
+            *concs += &(self.rate(concs) * dt);
+            self.hold(concs);
+        }
+
+        steps
+    }
+}
+
+/// Determine the indices of the neighbouring cells within the grid, and their separation.
+#[inline]
+#[must_use]
+pub fn neighbours(
+    index: [usize; 3],
+    res: [usize; 3],
+    cell_size: &Vector3<f64>,
+) -> Vec<([usize; 3], f64)> {
+    let mut neighbours = Vec::with_capacity(6);
+
+    for (dim, ((i, n), dx)) in index
+        .iter()
+        .zip(res.iter())
+        .zip(cell_size.iter())
+        .enumerate()
+    {
+        if *i > 0 {
+            let mut next = index;
+            *next.get_mut(dim).expect("Invalid dimension.") -= 1;
+            neighbours.push((next, *dx));
+        }
+        if i + 1 < *n {
+            let mut next = index;
+            *next.get_mut(dim).expect("Invalid dimension.") += 1;
+            neighbours.push((next, *dx));
+        }
+    }
+
+    neighbours
+}
+
+/// Determine the cells of the inside material of an interface, given by name or surface name,
+/// which share a face with a cell of its outside material, and their exposed area per unit volume. [m^-1]
+#[inline]
+#[must_use]
+pub fn exposed(
+    inter: &Name,
+    verse: &Verse,
+    grid: &Regular,
+    cell_size: &Vector3<f64>,
+) -> Vec<([usize; 3], f64)> {
+    let inter = verse
+        .inters()
+        .map()
+        .iter()
+        .find(|(name, i)| *name == inter || i.surf() == inter)
+        .map(|(_name, i)| i)
+        .expect("Unknown interface.");
+
+    let res = grid.res();
+    let mut cells = Vec::new();
+    for ((xi, yi, zi), cell) in grid.cells().indexed_iter() {
+        if cell.mat() != inter.in_mat() {
+            continue;
+        }
+
+        let exposure: f64 = neighbours([xi, yi, zi], res, cell_size)
+            .iter()
+            .filter(|(next, _dx)| {
+                grid.cells().get(*next).expect("Invalid cell index.").mat() == inter.out_mat()
+            })
+            .map(|(_next, dx)| 1.0 / dx)
+            .sum();
+        if exposure > 0.0 {
+            cells.push(([xi, yi, zi], exposure));
+        }
+    }
+
+    cells
+}
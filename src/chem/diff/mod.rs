@@ -0,0 +1,8 @@
+//! Diffusion sub-module.
+
+pub mod boundary;
+pub mod diffusion;
+pub mod perfusion;
+pub mod topical;
+
+pub use self::{boundary::*, diffusion::*, perfusion::*, topical::*};
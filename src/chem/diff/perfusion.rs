@@ -0,0 +1,43 @@
+//! Perfusion implementation.
+
+use crate::{access, dom::Name};
+use attr::json;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Blood perfusion exchanging species with arterial blood.
+/// Acts as a volumetric source, S = w (c_art - c).
+#[json]
+pub struct Perfusion {
+    /// Perfusion rate. [s^-1]
+    rate: f64,
+    /// Arterial concentrations of each supplied species.
+    arterial: BTreeMap<Name, f64>,
+}
+
+impl Perfusion {
+    access!(rate, f64);
+    access!(arterial, BTreeMap<Name, f64>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(rate: f64, arterial: BTreeMap<Name, f64>) -> Self {
+        assert!(rate >= 0.0);
+
+        Self { rate, arterial }
+    }
+}
+
+impl Display for Perfusion {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "Perfusion {} s^-1:", self.rate)?;
+        for (name, conc) in &self.arterial {
+            write!(fmt, " {} ({})", name, conc)?;
+        }
+
+        Ok(())
+    }
+}
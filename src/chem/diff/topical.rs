@@ -0,0 +1,187 @@
+//! Topical implementation.
+
+use crate::{
+    access,
+    chem::exposed,
+    dom::{index_of_name, Name, Regular},
+    uni::Verse,
+};
+use attr::json;
+use nalgebra::Vector3;
+use ndarray::Array4;
+use std::fmt::{Display, Formatter, Result};
+
+/// Single application of a cream.
+#[json]
+pub struct Application {
+    /// Application time. [s]
+    start: f64,
+    /// Removal time. [s]
+    end: f64,
+    /// Applied amount per unit area. [conc m]
+    dose: f64,
+}
+
+impl Application {
+    access!(start, f64);
+    access!(end, f64);
+    access!(dose, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(start: f64, end: f64, dose: f64) -> Self {
+        assert!(start >= 0.0);
+        assert!(end > start);
+        assert!(dose >= 0.0);
+
+        Self { start, end, dose }
+    }
+}
+
+/// Topically applied cream releasing a species across an interface.
+#[json]
+pub struct Topical {
+    /// Interface, by name or surface name, the cream is applied to.
+    inter: Name,
+    /// Released species.
+    spec: Name,
+    /// First-order release rate of the cream reservoir. [s^-1]
+    release: f64,
+    /// Application schedule.
+    schedule: Vec<Application>,
+}
+
+impl Topical {
+    access!(inter, Name);
+    access!(spec, Name);
+    access!(release, f64);
+    access!(schedule, Vec<Application>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(inter: Name, spec: Name, release: f64, schedule: Vec<Application>) -> Self {
+        assert!(release >= 0.0);
+
+        Self {
+            inter,
+            spec,
+            release,
+            schedule,
+        }
+    }
+}
+
+impl Display for Topical {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "{} on {}\trelease: {} s^-1\tapplications: {}",
+            self.spec,
+            self.inter,
+            self.release,
+            self.schedule.len()
+        )
+    }
+}
+
+/// Cream reservoir feeding the cells on the inside of its interface.
+pub struct Cream {
+    /// Index of the released species.
+    spec: usize,
+    /// Receiving cells and their exposed area per unit volume. [m^-1]
+    cells: Vec<([usize; 3], f64)>,
+    /// First-order release rate. [s^-1]
+    release: f64,
+    /// Application schedule.
+    schedule: Vec<(f64, f64, f64)>,
+    /// Remaining cream per unit area of each application. [conc m]
+    reservoirs: Vec<f64>,
+    /// Current time. [s]
+    time: f64,
+}
+
+impl Cream {
+    access!(cells, Vec<([usize; 3], f64)>);
+    access!(reservoirs, Vec<f64>);
+    access!(time, f64);
+
+    /// Construct a new instance.
+    /// Cells of the inside material receive cream through each face shared with a cell of the outside material.
+    #[inline]
+    #[must_use]
+    pub fn new(topical: &Topical, verse: &Verse, grid: &Regular, cell_size: &Vector3<f64>) -> Self {
+        let cells = exposed(topical.inter(), verse, grid, cell_size);
+        assert!(
+            !cells.is_empty(),
+            "Topical interface does not cross the grid."
+        );
+
+        let mut cream = Self {
+            spec: index_of_name(&topical.spec, verse.specs()),
+            cells,
+            release: topical.release,
+            schedule: topical
+                .schedule
+                .iter()
+                .map(|app| (app.start, app.end, app.dose))
+                .collect(),
+            reservoirs: vec![0.0; topical.schedule.len()],
+            time: 0.0,
+        };
+        cream.events();
+
+        cream
+    }
+
+    /// Calculate the total remaining cream per unit area. [conc m]
+    #[inline]
+    #[must_use]
+    pub fn reservoir(&self) -> f64 {
+        self.reservoirs.iter().sum()
+    }
+
+    /// Remove, and then apply, the cream of applications ending or starting at the current time.
+    /// Each application keeps its own reservoir, so removing one leaves any overlapping applications in place.
+    fn events(&mut self) {
+        for ((start, end, dose), reservoir) in self.schedule.iter().zip(&mut self.reservoirs) {
+            if (*end - self.time).abs() < std::f64::EPSILON {
+                *reservoir = 0.0;
+            } else if (*start - self.time).abs() < std::f64::EPSILON {
+                *reservoir = *dose;
+            }
+        }
+    }
+
+    /// Release the cream into the receiving cells over a given duration.
+    #[inline]
+    pub fn release(&mut self, concs: &mut Array4<f64>, duration: f64) {
+        assert!(duration >= 0.0);
+
+        let end = self.time + duration;
+        while self.time < end {
+            let next = self
+                .schedule
+                .iter()
+                .flat_map(|(start, end, _dose)| vec![*start, *end])
+                .filter(|t| *t > self.time)
+                .fold(end, f64::min);
+
+            let frac = 1.0 - (-self.release * (next - self.time)).exp();
+            let mut released = 0.0;
+            for reservoir in &mut self.reservoirs {
+                released += *reservoir * frac;
+                *reservoir -= *reservoir * frac;
+            }
+            for ([xi, yi, zi], exposure) in &self.cells {
+                *concs
+                    .get_mut([*xi, *yi, *zi, self.spec])
+                    .expect("Invalid cell index.") += released * exposure;
+            }
+
+            self.time = next;
+            self.events();
+        }
+    }
+}
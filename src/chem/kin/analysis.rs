@@ -0,0 +1,213 @@
+//! Analysis implementation.
+
+use crate::{
+    access,
+    chem::{Network, Rate, Reaction, Species},
+    dom::{Name, Set},
+    math::null_space,
+};
+use std::fmt::{Display, Formatter, Result};
+
+/// Magnitude below which moiety coefficients are treated as zero.
+const COEFF_TOL: f64 = 1.0e-9;
+
+/// Stoichiometric analysis of a reaction network.
+pub struct Analysis {
+    /// Conserved moieties, as weighted sums of species whose total is unchanged by any reaction.
+    moieties: Vec<Vec<(Name, f64)>>,
+    /// Species which are consumed but never produced.
+    never_produced: Vec<Name>,
+    /// Species which are produced but never consumed.
+    never_consumed: Vec<Name>,
+    /// Problems which prevent the network from being simulated.
+    errors: Vec<String>,
+    /// Potential problems which may indicate a mistake.
+    warnings: Vec<String>,
+}
+
+impl Analysis {
+    access!(moieties, Vec<Vec<(Name, f64)>>);
+    access!(never_produced, Vec<Name>);
+    access!(never_consumed, Vec<Name>);
+    access!(errors, Vec<String>);
+    access!(warnings, Vec<String>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(reacts: &Set<Reaction>, specs: &Set<Species>) -> Self {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (name, react) in reacts.map() {
+            check(name, react, specs, &mut errors, &mut warnings);
+        }
+
+        if !errors.is_empty() {
+            return Self {
+                moieties: vec![],
+                never_produced: vec![],
+                never_consumed: vec![],
+                errors,
+                warnings,
+            };
+        }
+
+        let stoich = Network::new(reacts, specs).stoich().clone();
+        let names: Vec<&Name> = specs.map().keys().collect();
+
+        let mut never_produced = Vec::new();
+        let mut never_consumed = Vec::new();
+        for (row, name) in stoich.outer_iter().zip(&names) {
+            let produced = row.iter().any(|x| *x > 0.0);
+            let consumed = row.iter().any(|x| *x < 0.0);
+
+            if consumed && !produced {
+                never_produced.push((*name).clone());
+            } else if produced && !consumed {
+                never_consumed.push((*name).clone());
+            } else if !produced && !consumed {
+                warnings.push(format!(
+                    "Species '{}' is never changed by any reaction.",
+                    name
+                ));
+            }
+        }
+
+        let moieties = null_space(&stoich.t().to_owned())
+            .into_iter()
+            .map(|moiety| {
+                let min = moiety
+                    .iter()
+                    .map(|x| x.abs())
+                    .filter(|x| *x > COEFF_TOL)
+                    .fold(std::f64::INFINITY, f64::min);
+                moiety
+                    .iter()
+                    .zip(&names)
+                    .filter(|(x, _)| x.abs() > COEFF_TOL)
+                    .map(|(x, name)| ((*name).clone(), x / min))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            moieties,
+            never_produced,
+            never_consumed,
+            errors,
+            warnings,
+        }
+    }
+}
+
+/// Check a single reaction for errors and likely mistakes.
+fn check(
+    name: &Name,
+    react: &Reaction,
+    specs: &Set<Species>,
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    if react.reactants().is_empty() && react.products().is_empty() {
+        errors.push(format!("Reaction '{}' has no reactants or products.", name));
+    }
+
+    for (spec, coeff) in react.reactants().iter().chain(react.products()) {
+        if *coeff <= 0 {
+            errors.push(format!(
+                "Reaction '{}' has a non-positive coefficient ({}) for species '{}'.",
+                name, coeff, spec
+            ));
+        }
+    }
+
+    for spec in react.req_species() {
+        if !specs.map().contains_key(&spec) {
+            errors.push(format!(
+                "Reaction '{}' requires unknown species '{}'.",
+                name, spec
+            ));
+        }
+    }
+
+    for param in react.rate().params() {
+        if !param.is_finite() || param < 0.0 {
+            errors.push(format!(
+                "Reaction '{}' has an invalid rate parameter ({}).",
+                name, param
+            ));
+        }
+    }
+
+    let reactants: Vec<&Name> = react.reactants().iter().map(|(spec, _)| spec).collect();
+    let products: Vec<&Name> = react.products().iter().map(|(spec, _)| spec).collect();
+    let (forward, backward) = match react.rate() {
+        Rate::Reversible(_kf, fs, _kb, bs) => (fs.clone(), bs.clone()),
+        rate => (rate.req_species(), vec![]),
+    };
+    for spec in &forward {
+        if !reactants.contains(&spec) {
+            warnings.push(format!(
+                "Reaction '{}' rate depends upon '{}', which is not a reactant.",
+                name, spec
+            ));
+        }
+    }
+    for spec in &backward {
+        if !products.contains(&spec) {
+            warnings.push(format!(
+                "Reaction '{}' backward rate depends upon '{}', which is not a product.",
+                name, spec
+            ));
+        }
+    }
+
+    if let Some(order) = react.rate().order() {
+        let molecularity: i32 = react.reactants().iter().map(|(_, coeff)| coeff).sum();
+        if order as i32 != molecularity {
+            warnings.push(format!(
+                "Reaction '{}' rate order ({}) differs from its molecularity ({}), so its rate constant has units of conc^{} s^-1.",
+                name,
+                order,
+                molecularity,
+                1 - order as i32
+            ));
+        }
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        writeln!(fmt, "Conserved moieties\t({}):", self.moieties.len())?;
+        for moiety in &self.moieties {
+            let terms: Vec<String> = moiety
+                .iter()
+                .map(|(name, coeff)| format!("{} {}", coeff, name))
+                .collect();
+            writeln!(fmt, "\t* {}", terms.join(" + "))?;
+        }
+
+        writeln!(fmt, "Never produced\t({}):", self.never_produced.len())?;
+        for name in &self.never_produced {
+            writeln!(fmt, "\t* {}", name)?;
+        }
+
+        writeln!(fmt, "Never consumed\t({}):", self.never_consumed.len())?;
+        for name in &self.never_consumed {
+            writeln!(fmt, "\t* {}", name)?;
+        }
+
+        writeln!(fmt, "Warnings\t({}):", self.warnings.len())?;
+        for warning in &self.warnings {
+            writeln!(fmt, "\t* {}", warning)?;
+        }
+
+        write!(fmt, "Errors\t({}):", self.errors.len())?;
+        for error in &self.errors {
+            write!(fmt, "\n\t* {}", error)?;
+        }
+
+        Ok(())
+    }
+}
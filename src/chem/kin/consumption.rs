@@ -0,0 +1,70 @@
+//! Consumption implementation.
+
+use crate::{
+    access,
+    chem::{Network, Rate, Reaction, Species},
+    dom::{Name, Set},
+};
+use attr::json;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Michaelis-Menten metabolic consumption of a species.
+#[json]
+pub struct Consumption {
+    /// Maximum consumption rate. [conc s^-1]
+    vmax: f64,
+    /// Half-maximal concentration.
+    km: f64,
+}
+
+impl Consumption {
+    access!(vmax, f64);
+    access!(km, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(vmax: f64, km: f64) -> Self {
+        assert!(vmax >= 0.0);
+        assert!(km > 0.0);
+
+        Self { vmax, km }
+    }
+
+    /// Form the reaction consuming the given species.
+    #[inline]
+    #[must_use]
+    pub fn react(&self, spec: &Name) -> Reaction {
+        Reaction::new(
+            vec![(spec.clone(), 1)],
+            vec![],
+            Rate::MichaelisMenten(self.vmax, self.km, spec.clone()),
+        )
+    }
+}
+
+impl Display for Consumption {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "Vmax: {}, Km: {}", self.vmax, self.km)
+    }
+}
+
+/// Form the metabolic network consuming each of the given species.
+#[inline]
+#[must_use]
+pub fn metabolism(consumption: &BTreeMap<Name, Consumption>, specs: &Set<Species>) -> Network {
+    let reacts = consumption
+        .iter()
+        .map(|(name, cons)| {
+            (
+                Name::new(&format!("{}_consumption", name)),
+                cons.react(name),
+            )
+        })
+        .collect();
+
+    Network::new(&Set::new(reacts), specs)
+}
@@ -0,0 +1,195 @@
+//! Law implementation.
+
+use crate::{
+    chem::{Local, Rate, Species},
+    dom::{index_of_name, Set},
+};
+use ndarray::Array1;
+use physical_constants::MOLAR_GAS_CONSTANT;
+
+/// Rate law referencing species by their index within the species set.
+pub enum Law {
+    /// Niladic function. f(cs) = k
+    Zeroth(f64),
+    /// Monadic. f(cs) = k[A]
+    First(f64, usize),
+    /// Dyadic. f(cs) = k[A][B]
+    Second(f64, usize, usize),
+    /// Triadic. f(cs) = k[A][B][C]
+    Third(f64, usize, usize, usize),
+    /// Polyadic. f(cs) = prod(k[n])
+    Poly(f64, Vec<usize>),
+    /// Light activated. f(cs) = k phi prod([n])
+    Fluence(f64, Vec<usize>),
+    /// Absorption activated. f(cs) = k A prod([n])
+    Absorbed(f64, Vec<usize>),
+    /// Michaelis-Menten. f(cs) = V[S] / (K + [S])
+    MichaelisMenten(f64, f64, usize),
+    /// Hill. f(cs) = V[S]^n / (K^n + [S]^n)
+    Hill(f64, f64, f64, usize),
+    /// Competitive inhibition. f(cs) = V[S] / (K (1 + [I] / Ki) + [S])
+    Inhibited(f64, f64, f64, usize, usize),
+    /// Arrhenius. f(cs) = A exp(-Ea / RT) prod([n])
+    Arrhenius(f64, f64, Vec<usize>),
+    /// Reversible. f(cs) = kf prod([f]) - kb prod([b])
+    Reversible(f64, Vec<usize>, f64, Vec<usize>),
+}
+
+impl Law {
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(rate: &Rate, specs: &Set<Species>) -> Self {
+        let index = |name| index_of_name(name, specs);
+
+        match rate {
+            Rate::Zeroth(k) => Self::Zeroth(*k),
+            Rate::First(k, a) => Self::First(*k, index(a)),
+            Rate::Second(k, a, b) => Self::Second(*k, index(a), index(b)),
+            Rate::Third(k, a, b, c) => Self::Third(*k, index(a), index(b), index(c)),
+            Rate::Poly(k, cs) => Self::Poly(*k, cs.iter().map(index).collect()),
+            Rate::Fluence(k, cs) => Self::Fluence(*k, cs.iter().map(index).collect()),
+            Rate::Absorbed(k, cs) => Self::Absorbed(*k, cs.iter().map(index).collect()),
+            Rate::MichaelisMenten(v, km, s) => Self::MichaelisMenten(*v, *km, index(s)),
+            Rate::Hill(v, k, n, s) => Self::Hill(*v, *k, *n, index(s)),
+            Rate::Inhibited(v, km, ki, s, i) => Self::Inhibited(*v, *km, *ki, index(s), index(i)),
+            Rate::Arrhenius(a, ea, cs) => Self::Arrhenius(*a, *ea, cs.iter().map(index).collect()),
+            Rate::Reversible(kf, fs, kb, bs) => Self::Reversible(
+                *kf,
+                fs.iter().map(index).collect(),
+                *kb,
+                bs.iter().map(index).collect(),
+            ),
+        }
+    }
+
+    /// Calculate the rate for the given concentrations and local conditions.
+    #[inline]
+    #[must_use]
+    pub fn rate(&self, concs: &Array1<f64>, local: &Local) -> f64 {
+        let conc = |i: &usize| *concs.get(*i).expect("Invalid species index.");
+        let poly = |k: f64, cs: &[usize]| cs.iter().fold(k, |prod, c| prod * conc(c));
+
+        match self {
+            Self::Zeroth(k) => *k,
+            Self::First(k, a) => k * conc(a),
+            Self::Second(k, a, b) => k * conc(a) * conc(b),
+            Self::Third(k, a, b, c) => k * conc(a) * conc(b) * conc(c),
+            Self::Poly(k, cs) => poly(*k, cs),
+            Self::Fluence(k, cs) => poly(k * local.fluence_rate(), cs),
+            Self::Absorbed(k, cs) => poly(k * local.abs_dens(), cs),
+            Self::MichaelisMenten(v, km, s) => v * conc(s) / (km + conc(s)),
+            Self::Hill(v, k, n, s) => {
+                let sn = conc(s).max(0.0).powf(*n);
+                v * sn / (k.powf(*n) + sn)
+            }
+            Self::Inhibited(v, km, ki, s, i) => {
+                v * conc(s) / (km * (1.0 + (conc(i) / ki)) + conc(s))
+            }
+            Self::Arrhenius(a, ea, cs) => poly(arrhenius(*a, *ea, *local.temp()), cs),
+            Self::Reversible(kf, fs, kb, bs) => poly(*kf, fs) - poly(*kb, bs),
+        }
+    }
+
+    /// Calculate the forward and backward stochastic propensities for the given molecule counts.
+    /// The system size is the number of molecules per unit concentration.
+    /// Mass-action laws use falling factorials of the counts, k n (n - 1) ... / size^(order - 1),
+    /// so a channel has no propensity without enough molecules to react.
+    /// Other laws follow their macroscopic rate.
+    #[inline]
+    #[must_use]
+    pub fn propensities(&self, counts: &Array1<f64>, size: f64, local: &Local) -> (f64, f64) {
+        let falling = |k: f64, cs: &[usize]| {
+            cs.iter().enumerate().fold(k * size, |prod, (i, c)| {
+                let prior = cs.iter().take(i).filter(|d| *d == c).count() as f64;
+                let count = *counts.get(*c).expect("Invalid species index.");
+                prod * (count - prior).max(0.0) / size
+            })
+        };
+
+        match self {
+            Self::Zeroth(k) => (k * size, 0.0),
+            Self::First(k, a) => (falling(*k, &[*a]), 0.0),
+            Self::Second(k, a, b) => (falling(*k, &[*a, *b]), 0.0),
+            Self::Third(k, a, b, c) => (falling(*k, &[*a, *b, *c]), 0.0),
+            Self::Poly(k, cs) => (falling(*k, cs), 0.0),
+            Self::Fluence(k, cs) => (falling(k * local.fluence_rate(), cs), 0.0),
+            Self::Absorbed(k, cs) => (falling(k * local.abs_dens(), cs), 0.0),
+            Self::Arrhenius(a, ea, cs) => (falling(arrhenius(*a, *ea, *local.temp()), cs), 0.0),
+            Self::Reversible(kf, fs, kb, bs) => (falling(*kf, fs), falling(*kb, bs)),
+            Self::MichaelisMenten(..) | Self::Hill(..) | Self::Inhibited(..) => {
+                (self.rate(&(counts / size), local) * size, 0.0)
+            }
+        }
+    }
+
+    /// Calculate the partial derivatives of the rate with respect to each species it depends upon.
+    /// Repeated species indices should be accumulated.
+    #[inline]
+    #[must_use]
+    pub fn grad(&self, concs: &Array1<f64>, local: &Local) -> Vec<(usize, f64)> {
+        let conc = |i: &usize| *concs.get(*i).expect("Invalid species index.");
+        let poly = |k: f64, cs: &[usize]| -> Vec<(usize, f64)> {
+            cs.iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    (
+                        *c,
+                        cs.iter()
+                            .enumerate()
+                            .filter(|(j, _)| *j != i)
+                            .fold(k, |prod, (_, d)| prod * conc(d)),
+                    )
+                })
+                .collect()
+        };
+
+        match self {
+            Self::Zeroth(_k) => vec![],
+            Self::First(k, a) => vec![(*a, *k)],
+            Self::Second(k, a, b) => vec![(*a, k * conc(b)), (*b, k * conc(a))],
+            Self::Third(k, a, b, c) => vec![
+                (*a, k * conc(b) * conc(c)),
+                (*b, k * conc(a) * conc(c)),
+                (*c, k * conc(a) * conc(b)),
+            ],
+            Self::Poly(k, cs) => poly(*k, cs),
+            Self::Fluence(k, cs) => poly(k * local.fluence_rate(), cs),
+            Self::Absorbed(k, cs) => poly(k * local.abs_dens(), cs),
+            Self::MichaelisMenten(v, km, s) => vec![(*s, v * km / (km + conc(s)).powi(2))],
+            Self::Hill(v, k, n, s) => {
+                let c = conc(s).max(0.0);
+                let kn = k.powf(*n);
+                let sn = c.powf(*n);
+                if c > 0.0 {
+                    vec![(*s, v * n * kn * sn / (c * (kn + sn).powi(2)))]
+                } else {
+                    vec![(*s, 0.0)]
+                }
+            }
+            Self::Inhibited(v, km, ki, s, i) => {
+                let km_eff = km * (1.0 + (conc(i) / ki));
+                let denom = (km_eff + conc(s)).powi(2);
+                vec![
+                    (*s, v * km_eff / denom),
+                    (*i, -v * conc(s) * km / (ki * denom)),
+                ]
+            }
+            Self::Arrhenius(a, ea, cs) => poly(arrhenius(*a, *ea, *local.temp()), cs),
+            Self::Reversible(kf, fs, kb, bs) => {
+                let mut grad = poly(*kf, fs);
+                grad.extend(poly(-kb, bs));
+                grad
+            }
+        }
+    }
+}
+
+/// Calculate an Arrhenius rate constant at a given temperature.
+#[inline]
+#[must_use]
+pub fn arrhenius(a: f64, ea: f64, temp: f64) -> f64 {
+    assert!(temp > 0.0);
+
+    a * (-ea / (MOLAR_GAS_CONSTANT * temp)).exp()
+}
@@ -0,0 +1,45 @@
+//! Local conditions implementation.
+
+use crate::access;
+
+/// Default local temperature, that of the human body. [K]
+pub const BODY_TEMP: f64 = 310.15;
+
+/// Local physical conditions influencing reaction rates within a cell.
+#[derive(Debug, Clone)]
+pub struct Local {
+    /// Fluence rate. [W m^-2]
+    fluence_rate: f64,
+    /// Absorbed power density. [W m^-3]
+    abs_dens: f64,
+    /// Temperature. [K]
+    temp: f64,
+}
+
+impl Local {
+    access!(fluence_rate, fluence_rate_mut, f64);
+    access!(abs_dens, abs_dens_mut, f64);
+    access!(temp, temp_mut, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(fluence_rate: f64, abs_dens: f64, temp: f64) -> Self {
+        assert!(fluence_rate >= 0.0);
+        assert!(abs_dens >= 0.0);
+        assert!(temp > 0.0);
+
+        Self {
+            fluence_rate,
+            abs_dens,
+            temp,
+        }
+    }
+}
+
+impl Default for Local {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0.0, 0.0, BODY_TEMP)
+    }
+}
@@ -0,0 +1,10 @@
+//! Kinetics sub-module.
+
+pub mod analysis;
+pub mod consumption;
+pub mod law;
+pub mod local;
+pub mod network;
+pub mod stochastic;
+
+pub use self::{analysis::*, consumption::*, law::*, local::*, network::*, stochastic::*};
@@ -0,0 +1,82 @@
+//! Network implementation.
+
+use crate::{
+    access,
+    chem::{Law, Local, Reaction, Species},
+    dom::{index_of_name, Set},
+};
+use ndarray::{Array1, Array2};
+
+/// Reaction network referencing species by their index within the species set.
+pub struct Network {
+    /// Rate law of each reaction.
+    laws: Vec<Law>,
+    /// Stoichiometric matrix. [species x reactions]
+    stoich: Array2<f64>,
+}
+
+impl Network {
+    access!(laws, Vec<Law>);
+    access!(stoich, Array2<f64>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(reacts: &Set<Reaction>, specs: &Set<Species>) -> Self {
+        let mut stoich = Array2::zeros((specs.map().len(), reacts.map().len()));
+
+        for (j, react) in reacts.map().values().enumerate() {
+            for (name, coeff) in react.reactants() {
+                *stoich
+                    .get_mut([index_of_name(name, specs), j])
+                    .expect("Invalid stoichiometry index.") -= f64::from(*coeff);
+            }
+            for (name, coeff) in react.products() {
+                *stoich
+                    .get_mut([index_of_name(name, specs), j])
+                    .expect("Invalid stoichiometry index.") += f64::from(*coeff);
+            }
+        }
+
+        Self {
+            laws: reacts
+                .map()
+                .values()
+                .map(|react| Law::new(react.rate(), specs))
+                .collect(),
+            stoich,
+        }
+    }
+
+    /// Calculate the rate of each reaction for the given concentrations and local conditions.
+    #[inline]
+    #[must_use]
+    pub fn rates(&self, concs: &Array1<f64>, local: &Local) -> Array1<f64> {
+        self.laws.iter().map(|law| law.rate(concs, local)).collect()
+    }
+
+    /// Calculate the rate of change of each species concentration.
+    /// Reaction rates are scaled by the given multiplier.
+    #[inline]
+    #[must_use]
+    pub fn deriv(&self, concs: &Array1<f64>, mult: f64, local: &Local) -> Array1<f64> {
+        self.stoich.dot(&self.rates(concs, local)) * mult
+    }
+
+    /// Calculate the Jacobian of the species rates of change with respect to the species concentrations.
+    /// Reaction rates are scaled by the given multiplier.
+    #[inline]
+    #[must_use]
+    pub fn jacobian(&self, concs: &Array1<f64>, mult: f64, local: &Local) -> Array2<f64> {
+        let num_specs = concs.len();
+        let mut grads = Array2::zeros((self.laws.len(), num_specs));
+
+        for (j, law) in self.laws.iter().enumerate() {
+            for (l, grad) in law.grad(concs, local) {
+                *grads.get_mut([j, l]).expect("Invalid species index.") += grad;
+            }
+        }
+
+        self.stoich.dot(&grads) * mult
+    }
+}
@@ -0,0 +1,238 @@
+//! Stochastic kinetics implementation.
+
+use crate::{
+    chem::{Local, Network},
+    math::poisson,
+};
+use attr::json;
+use ndarray::Array1;
+use rand::{rngs::ThreadRng, Rng};
+use std::fmt::{Display, Formatter, Result};
+
+/// Stochastic simulation methods.
+#[json]
+pub enum Method {
+    /// Exact Gillespie stochastic simulation algorithm.
+    Gillespie,
+    /// Poisson tau-leaping. (leap duration [s])
+    TauLeap(f64),
+}
+
+impl Method {
+    /// Evolve the molecule counts over a given duration.
+    /// Returns the number of reaction events, or leaps, taken.
+    #[inline]
+    pub fn evolve(
+        &self,
+        rng: &mut ThreadRng,
+        net: &Network,
+        counts: &mut Array1<f64>,
+        size: f64,
+        local: &Local,
+        duration: f64,
+    ) -> usize {
+        match self {
+            Self::Gillespie => gillespie(rng, net, counts, size, local, duration),
+            Self::TauLeap(tau) => tau_leap(rng, net, counts, size, local, duration, *tau),
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Gillespie => write!(fmt, "Gillespie"),
+            Self::TauLeap(tau) => write!(fmt, "Tau-leaping ({}s)", tau),
+        }
+    }
+}
+
+/// Calculate the propensity of each reaction channel, the forward and then backward direction of each reaction.
+/// The system size is the number of molecules per unit concentration,
+/// so propensities follow the deterministic rate laws in the macroscopic limit.
+#[inline]
+#[must_use]
+pub fn propensities(net: &Network, counts: &Array1<f64>, size: f64, local: &Local) -> Vec<f64> {
+    assert!(size > 0.0);
+
+    net.laws()
+        .iter()
+        .flat_map(|law| {
+            let (forward, backward) = law.propensities(counts, size, local);
+            vec![forward.max(0.0), backward.max(0.0)]
+        })
+        .collect()
+}
+
+/// Fire a reaction channel a number of times.
+fn fire(net: &Network, counts: &mut Array1<f64>, channel: usize, times: f64) {
+    let sign = if channel % 2 == 0 { 1.0 } else { -1.0 };
+    counts.scaled_add(sign * times, &net.stoich().column(channel / 2));
+}
+
+/// Evolve the molecule counts over a given duration with the Gillespie stochastic simulation algorithm.
+/// Returns the number of reaction events.
+#[inline]
+pub fn gillespie(
+    rng: &mut ThreadRng,
+    net: &Network,
+    counts: &mut Array1<f64>,
+    size: f64,
+    local: &Local,
+    duration: f64,
+) -> usize {
+    assert!(duration >= 0.0);
+
+    let mut time = 0.0;
+    let mut events = 0;
+    loop {
+        let props = propensities(net, counts, size, local);
+        let total: f64 = props.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+
+        time += -(1.0 - rng.gen_range(0.0_f64, 1.0)).ln() / total;
+        if time > duration {
+            break;
+        }
+
+        let target = rng.gen_range(0.0, total);
+        let mut sum = 0.0;
+        let mut channel = props.len() - 1;
+        for (c, prop) in props.iter().enumerate() {
+            sum += prop;
+            if sum > target {
+                channel = c;
+                break;
+            }
+        }
+
+        fire(net, counts, channel, 1.0);
+        assert!(
+            counts.iter().all(|n| *n >= 0.0),
+            "Reaction channel {} fired without enough molecules.",
+            channel
+        );
+        events += 1;
+    }
+
+    events
+}
+
+/// Evolve the molecule counts over a given duration by Poisson tau-leaping.
+/// Leaps which would make a count negative are rejected and retried with half the duration.
+/// Returns the number of leaps taken.
+#[inline]
+pub fn tau_leap(
+    rng: &mut ThreadRng,
+    net: &Network,
+    counts: &mut Array1<f64>,
+    size: f64,
+    local: &Local,
+    duration: f64,
+    tau: f64,
+) -> usize {
+    assert!(duration >= 0.0);
+    assert!(tau > 0.0);
+
+    let mut time = 0.0;
+    let mut leap = tau;
+    let mut leaps = 0;
+    while time < duration {
+        let dt = leap.min(duration - time);
+
+        let mut next = counts.clone();
+        for (channel, prop) in propensities(net, counts, size, local).iter().enumerate() {
+            let times = poisson(rng, prop * dt);
+            if times > 0.0 {
+                fire(net, &mut next, channel, times);
+            }
+        }
+
+        if next.iter().any(|n| *n < 0.0) {
+            leap = dt / 2.0;
+            continue;
+        }
+
+        *counts = next;
+        time += dt;
+        leap = tau;
+        leaps += 1;
+    }
+
+    leaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chem::{Reaction, Species},
+        dom::{Name, Set},
+    };
+    use std::collections::BTreeMap;
+
+    /// Form a network of the given reaction equations over the given species.
+    fn network(eqs: &[&str], specs: &[&str]) -> Network {
+        let reacts = eqs
+            .iter()
+            .enumerate()
+            .map(|(i, eq)| {
+                let react: Reaction = eq.parse().expect("Invalid reaction.");
+                (Name::new(&format!("r{}", i)), react)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let specs = specs
+            .iter()
+            .map(|name| (Name::new(name), Species::new(None)))
+            .collect::<BTreeMap<_, _>>();
+
+        Network::new(&Set::new(reacts), &Set::new(specs))
+    }
+
+    #[test]
+    fn decay_mean() {
+        let net = network(&["a -> ; k = 0.5 [a]"], &["a"]);
+        let local = Local::new(0.0, 0.0, 310.0);
+        let (init, runs, duration) = (100.0, 2000, 2.0);
+
+        let mut rng = rand::thread_rng();
+        let mut total = 0.0;
+        for _ in 0..runs {
+            let mut counts = Array1::from(vec![init]);
+            let _events = gillespie(&mut rng, &net, &mut counts, 1.0, &local, duration);
+            total += counts.sum();
+        }
+        let mean = total / f64::from(runs);
+
+        let exact = init * (-0.5 * duration).exp();
+        let std_err = (init * (-0.5 * duration).exp() * (1.0 - (-0.5 * duration).exp())
+            / f64::from(runs))
+        .sqrt();
+        assert!((mean - exact).abs() < 5.0 * std_err);
+    }
+
+    #[test]
+    fn dimerisation_non_negative() {
+        let net = network(&["2 a -> b ; k = 10 [a] [a]"], &["a", "b"]);
+        let local = Local::new(0.0, 0.0, 310.0);
+
+        let props = propensities(&net, &Array1::from(vec![1.0, 0.0]), 1.0, &local);
+        assert!(props.iter().all(|p| *p <= 0.0));
+
+        let mut rng = rand::thread_rng();
+        for init in &[1.0, 2.0, 3.0, 11.0] {
+            let mut counts = Array1::from(vec![*init, 0.0]);
+            let _events = gillespie(&mut rng, &net, &mut counts, 1.0, &local, 100.0);
+            assert!(counts.iter().all(|n| *n >= 0.0));
+            assert!(
+                (counts.get(0).expect("Missing count.") - (init % 2.0)).abs() < std::f64::EPSILON
+            );
+
+            let mut counts = Array1::from(vec![*init, 0.0]);
+            let _leaps = tau_leap(&mut rng, &net, &mut counts, 1.0, &local, 100.0, 0.01);
+            assert!(counts.iter().all(|n| *n >= 0.0));
+        }
+    }
+}
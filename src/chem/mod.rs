@@ -1,5 +1,8 @@
 //! Chemistry module.
 
+pub mod diff;
+pub mod kin;
+pub mod pk;
 pub mod rct;
 
-pub use self::rct::*;
+pub use self::{diff::*, kin::*, pk::*, rct::*};
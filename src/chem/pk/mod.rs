@@ -0,0 +1,6 @@
+//! Pharmacokinetics sub-module.
+
+pub mod pharmacokinetics;
+pub mod plasma;
+
+pub use self::{pharmacokinetics::*, plasma::*};
@@ -0,0 +1,96 @@
+//! Pharmacokinetics implementation.
+
+use crate::{access, dom::Name};
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Bolus dose administered into the plasma.
+#[json]
+pub struct Dose {
+    /// Administration time. [s]
+    time: f64,
+    /// Administered amount. [conc m^3]
+    amount: f64,
+}
+
+impl Dose {
+    access!(time, f64);
+    access!(amount, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(time: f64, amount: f64) -> Self {
+        assert!(time >= 0.0);
+        assert!(amount >= 0.0);
+
+        Self { time, amount }
+    }
+}
+
+/// Two compartment, plasma and interstitial, pharmacokinetic model of a systemically administered species.
+#[json]
+pub struct Pharmacokinetics {
+    /// Administered species.
+    spec: Name,
+    /// Plasma volume. [m^3]
+    plasma_vol: f64,
+    /// Interstitial volume. [m^3]
+    inter_vol: f64,
+    /// Inter-compartmental clearance. [m^3 s^-1]
+    clearance: f64,
+    /// Plasma elimination rate. [s^-1]
+    elim: f64,
+    /// Dosing schedule.
+    doses: Vec<Dose>,
+}
+
+impl Pharmacokinetics {
+    access!(spec, Name);
+    access!(plasma_vol, f64);
+    access!(inter_vol, f64);
+    access!(clearance, f64);
+    access!(elim, f64);
+    access!(doses, Vec<Dose>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        spec: Name,
+        plasma_vol: f64,
+        inter_vol: f64,
+        clearance: f64,
+        elim: f64,
+        doses: Vec<Dose>,
+    ) -> Self {
+        assert!(plasma_vol > 0.0);
+        assert!(inter_vol > 0.0);
+        assert!(clearance >= 0.0);
+        assert!(elim >= 0.0);
+
+        Self {
+            spec,
+            plasma_vol,
+            inter_vol,
+            clearance,
+            elim,
+            doses,
+        }
+    }
+}
+
+impl Display for Pharmacokinetics {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "{}\tplasma: {} m^3\tinterstitial: {} m^3\tclearance: {} m^3 s^-1\telimination: {} s^-1\tdoses: {}",
+            self.spec,
+            self.plasma_vol,
+            self.inter_vol,
+            self.clearance,
+            self.elim,
+            self.doses.len()
+        )
+    }
+}
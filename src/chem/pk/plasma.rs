@@ -0,0 +1,149 @@
+//! Plasma implementation.
+
+use crate::{
+    access,
+    chem::Pharmacokinetics,
+    dom::{index_of_name, Regular},
+    uni::Verse,
+};
+use ndarray::{arr1, arr2, Array1, Array2, Array4};
+
+/// Plasma and interstitial compartments exchanging a species with the vascular cells of the grid.
+/// Uptake by the grid is assumed small enough not to deplete the plasma.
+pub struct Plasma {
+    /// Index of the administered species.
+    spec: usize,
+    /// Vascular cells and their plasma exchange rates. [s^-1]
+    cells: Vec<([usize; 3], f64)>,
+    /// Compartment rate matrix. [s^-1]
+    rates: Array2<f64>,
+    /// Dosing schedule. (time, plasma concentration increase)
+    doses: Vec<(f64, f64)>,
+    /// Plasma and interstitial concentrations.
+    concs: Array1<f64>,
+    /// Current time. [s]
+    time: f64,
+}
+
+impl Plasma {
+    access!(cells, Vec<([usize; 3], f64)>);
+    access!(concs, Array1<f64>);
+    access!(time, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(pk: &Pharmacokinetics, verse: &Verse, grid: &Regular) -> Self {
+        let mut cells = Vec::new();
+        for ((xi, yi, zi), cell) in grid.cells().indexed_iter() {
+            if let Some(rate) = verse
+                .mats()
+                .map()
+                .get(cell.mat())
+                .expect("Invalid material name.")
+                .vascular()
+            {
+                cells.push(([xi, yi, zi], *rate));
+            }
+        }
+
+        let kp = pk.clearance() / pk.plasma_vol();
+        let ki = pk.clearance() / pk.inter_vol();
+
+        let mut plasma = Self {
+            spec: index_of_name(pk.spec(), verse.specs()),
+            cells,
+            rates: arr2(&[[-(pk.elim() + kp), kp], [ki, -ki]]),
+            doses: pk
+                .doses()
+                .iter()
+                .map(|dose| (*dose.time(), dose.amount() / pk.plasma_vol()))
+                .collect(),
+            concs: arr1(&[0.0, 0.0]),
+            time: 0.0,
+        };
+        plasma.administer();
+
+        plasma
+    }
+
+    /// Get the plasma concentration.
+    #[inline]
+    #[must_use]
+    pub fn plasma(&self) -> f64 {
+        *self.concs.get(0).expect("Missing plasma concentration.")
+    }
+
+    /// Get the interstitial concentration.
+    #[inline]
+    #[must_use]
+    pub fn inter(&self) -> f64 {
+        *self
+            .concs
+            .get(1)
+            .expect("Missing interstitial concentration.")
+    }
+
+    /// Administer the doses given at the current time.
+    fn administer(&mut self) {
+        for (time, conc) in &self.doses {
+            if (*time - self.time).abs() < std::f64::EPSILON {
+                *self
+                    .concs
+                    .get_mut(0)
+                    .expect("Missing plasma concentration.") += conc;
+            }
+        }
+    }
+
+    /// Calculate the compartment propagator, exp(A dt), of the rate matrix.
+    /// The divided difference of the eigenvalue exponentials is formed with exp_m1,
+    /// so that nearly repeated eigenvalues do not lose precision.
+    fn propagator(&self, dt: f64) -> Array2<f64> {
+        let a = &self.rates;
+        let entry = |r: usize, c: usize| *a.get([r, c]).expect("Invalid rate index.");
+        let trace = entry(0, 0) + entry(1, 1);
+        let det = (entry(0, 0) * entry(1, 1)) - (entry(0, 1) * entry(1, 0));
+        let root = ((trace * trace / 4.0) - det).max(0.0).sqrt();
+        let (l1, l2) = ((trace / 2.0) + root, (trace / 2.0) - root);
+
+        let e2 = (l2 * dt).exp();
+        let delta = l1 - l2;
+        let div = if delta > 0.0 {
+            e2 * (delta * dt).exp_m1() / delta
+        } else {
+            e2 * dt
+        };
+
+        (Array2::eye(2) * (e2 - (l2 * div))) + (a * div)
+    }
+
+    /// Evolve the compartments, and exchange with the vascular cells, over a given duration.
+    #[inline]
+    pub fn exchange(&mut self, concs: &mut Array4<f64>, duration: f64) {
+        assert!(duration >= 0.0);
+
+        let end = self.time + duration;
+        while self.time < end {
+            let next = self
+                .doses
+                .iter()
+                .map(|(time, _conc)| *time)
+                .filter(|time| *time > self.time)
+                .fold(end, f64::min);
+            let dt = next - self.time;
+
+            self.concs = self.propagator(dt).dot(&self.concs);
+            let plasma = self.plasma();
+            for ([xi, yi, zi], rate) in &self.cells {
+                let conc = concs
+                    .get_mut([*xi, *yi, *zi, self.spec])
+                    .expect("Invalid cell index.");
+                *conc += (plasma - *conc) * (1.0 - (-rate * dt).exp());
+            }
+
+            self.time = next;
+            self.administer();
+        }
+    }
+}
@@ -2,7 +2,10 @@
 
 use crate::dom::Name;
 use attr::json;
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
 
 /// Rates that accept a single scalar value, and return a single scalar value.
 #[json]
@@ -17,6 +20,20 @@ pub enum Rate {
     Third(f64, Name, Name, Name),
     /// Polyadic. f(cs) = prod(k[n])
     Poly(f64, Vec<Name>),
+    /// Light activated. f(cs) = k phi prod([n]), where phi is the local fluence rate.
+    Fluence(f64, Vec<Name>),
+    /// Absorption activated. f(cs) = k A prod([n]), where A is the local absorbed power density.
+    Absorbed(f64, Vec<Name>),
+    /// Michaelis-Menten. f(cs) = V[S] / (K + [S]) (maximum rate, Michaelis constant, substrate)
+    MichaelisMenten(f64, f64, Name),
+    /// Hill. f(cs) = V[S]^n / (K^n + [S]^n) (maximum rate, half-saturation constant, Hill coefficient, substrate)
+    Hill(f64, f64, f64, Name),
+    /// Competitive inhibition. f(cs) = V[S] / (K (1 + [I] / Ki) + [S]) (maximum rate, Michaelis constant, inhibition constant, substrate, inhibitor)
+    Inhibited(f64, f64, f64, Name, Name),
+    /// Arrhenius. f(cs) = A exp(-Ea / RT) prod([n]) (pre-exponential factor, activation energy [J mol^-1], species)
+    Arrhenius(f64, f64, Vec<Name>),
+    /// Reversible. f(cs) = kf prod([f]) - kb prod([b]) (forward rate, forward species, backward rate, backward species)
+    Reversible(f64, Vec<Name>, f64, Vec<Name>),
 }
 
 impl Rate {
@@ -29,7 +46,101 @@ impl Rate {
             Self::First(_k, a) => vec![a.clone()],
             Self::Second(_k, a, b) => vec![a.clone(), b.clone()],
             Self::Third(_k, a, b, c) => vec![a.clone(), b.clone(), c.clone()],
-            Self::Poly(_k, cs) => cs.clone(),
+            Self::Poly(_k, cs)
+            | Self::Fluence(_k, cs)
+            | Self::Absorbed(_k, cs)
+            | Self::Arrhenius(_k, _, cs) => cs.clone(),
+            Self::MichaelisMenten(_v, _km, s) | Self::Hill(_v, _km, _, s) => vec![s.clone()],
+            Self::Inhibited(_v, _km, _ki, s, i) => vec![s.clone(), i.clone()],
+            Self::Reversible(_kf, fs, _kb, bs) => {
+                let mut cs = fs.clone();
+                cs.extend(bs.iter().cloned());
+                cs.sort();
+                cs.dedup();
+                cs
+            }
+        }
+    }
+
+    /// Determine the overall order of a mass-action rate.
+    /// Saturating and reversible rates have no single order.
+    #[inline]
+    #[must_use]
+    pub fn order(&self) -> Option<usize> {
+        match self {
+            Self::Zeroth(_k) => Some(0),
+            Self::First(..) => Some(1),
+            Self::Second(..) => Some(2),
+            Self::Third(..) => Some(3),
+            Self::Poly(_k, cs)
+            | Self::Fluence(_k, cs)
+            | Self::Absorbed(_k, cs)
+            | Self::Arrhenius(_k, _, cs) => Some(cs.len()),
+            Self::MichaelisMenten(..)
+            | Self::Hill(..)
+            | Self::Inhibited(..)
+            | Self::Reversible(..) => None,
+        }
+    }
+
+    /// Get a list of the numerical parameters of the rate.
+    #[inline]
+    #[must_use]
+    pub fn params(&self) -> Vec<f64> {
+        match self {
+            Self::Zeroth(k)
+            | Self::First(k, _)
+            | Self::Second(k, _, _)
+            | Self::Third(k, _, _, _)
+            | Self::Poly(k, _)
+            | Self::Fluence(k, _)
+            | Self::Absorbed(k, _) => vec![*k],
+            Self::MichaelisMenten(v, km, _) => vec![*v, *km],
+            Self::Hill(v, k, n, _) => vec![*v, *k, *n],
+            Self::Inhibited(v, km, ki, _, _) => vec![*v, *km, *ki],
+            Self::Arrhenius(a, ea, _) => vec![*a, *ea],
+            Self::Reversible(kf, _, kb, _) => vec![*kf, *kb],
+        }
+    }
+
+    /// Construct a copy of the rate with its numerical parameters replaced.
+    /// Parameters are given in the same order as returned by `params`.
+    #[inline]
+    #[must_use]
+    pub fn with_params(&self, ps: &[f64]) -> Self {
+        assert!(ps.len() == self.params().len());
+        let p = |i: usize| *ps.get(i).expect("Missing rate parameter.");
+
+        match self {
+            Self::Zeroth(_k) => Self::Zeroth(p(0)),
+            Self::First(_k, a) => Self::First(p(0), a.clone()),
+            Self::Second(_k, a, b) => Self::Second(p(0), a.clone(), b.clone()),
+            Self::Third(_k, a, b, c) => Self::Third(p(0), a.clone(), b.clone(), c.clone()),
+            Self::Poly(_k, cs) => Self::Poly(p(0), cs.clone()),
+            Self::Fluence(_k, cs) => Self::Fluence(p(0), cs.clone()),
+            Self::Absorbed(_k, cs) => Self::Absorbed(p(0), cs.clone()),
+            Self::MichaelisMenten(_v, _km, s) => Self::MichaelisMenten(p(0), p(1), s.clone()),
+            Self::Hill(_v, _k, _n, s) => Self::Hill(p(0), p(1), p(2), s.clone()),
+            Self::Inhibited(_v, _km, _ki, s, i) => {
+                Self::Inhibited(p(0), p(1), p(2), s.clone(), i.clone())
+            }
+            Self::Arrhenius(_a, _ea, cs) => Self::Arrhenius(p(0), p(1), cs.clone()),
+            Self::Reversible(_kf, fs, _kb, bs) => {
+                Self::Reversible(p(0), fs.clone(), p(1), bs.clone())
+            }
+        }
+    }
+
+    /// Decompose a mass-action rate into its rate constant and list of species.
+    #[inline]
+    pub fn mass_action(&self) -> std::result::Result<(f64, Vec<Name>), String> {
+        match self {
+            Self::Zeroth(k) => Ok((*k, vec![])),
+            Self::First(k, a) => Ok((*k, vec![a.clone()])),
+            Self::Second(k, a, b) => Ok((*k, vec![a.clone(), b.clone()])),
+            Self::Third(k, a, b, c) => Ok((*k, vec![a.clone(), b.clone(), c.clone()])),
+            Self::Poly(k, cs) => Ok((*k, cs.clone())),
+            _ => Err(format!("Rate is not of mass-action form: {}", self)),
         }
     }
 }
@@ -48,6 +159,244 @@ impl Display for Rate {
                 }
                 write!(fmt, "")
             }
+            Self::Fluence(k, cs) => {
+                write!(fmt, "{} phi", k)?;
+                for c in cs {
+                    write!(fmt, " [{}]", c)?;
+                }
+                write!(fmt, "")
+            }
+            Self::Absorbed(k, cs) => {
+                write!(fmt, "{} A", k)?;
+                for c in cs {
+                    write!(fmt, " [{}]", c)?;
+                }
+                write!(fmt, "")
+            }
+            Self::MichaelisMenten(v, km, s) => write!(fmt, "{} [{}] / ({} + [{}])", v, s, km, s),
+            Self::Hill(v, k, n, s) => {
+                write!(fmt, "{} [{}]^{} / ({}^{} + [{}]^{})", v, s, n, k, n, s, n)
+            }
+            Self::Inhibited(v, km, ki, s, i) => write!(
+                fmt,
+                "{} [{}] / ({} (1 + [{}] / {}) + [{}])",
+                v, s, km, i, ki, s
+            ),
+            Self::Arrhenius(a, ea, cs) => {
+                write!(fmt, "{} exp(-{} / RT)", a, ea)?;
+                for c in cs {
+                    write!(fmt, " [{}]", c)?;
+                }
+                write!(fmt, "")
+            }
+            Self::Reversible(kf, fs, kb, bs) => {
+                write!(fmt, "{}", kf)?;
+                for c in fs {
+                    write!(fmt, " [{}]", c)?;
+                }
+                write!(fmt, " - {}", kb)?;
+                for c in bs {
+                    write!(fmt, " [{}]", c)?;
+                }
+                write!(fmt, "")
+            }
+        }
+    }
+}
+
+/// Rate expression lexical tokens.
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// Numerical value.
+    Num(f64),
+    /// Bracketed species concentration.
+    Spec(Name),
+    /// Bare word.
+    Word(String),
+    /// Single character symbol.
+    Sym(char),
+}
+
+/// Split a rate expression into tokens.
+fn tokenise(expr: &str) -> std::result::Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while let Some(c) = chars.get(i) {
+        if c.is_whitespace() {
+            i += 1;
+        } else if *c == '[' {
+            let len = chars
+                .iter()
+                .skip(i)
+                .position(|c| *c == ']')
+                .ok_or_else(|| format!("Unclosed species bracket in: {}", expr))?;
+            let name: String = chars.iter().skip(i + 1).take(len - 1).collect();
+            tokens.push(Token::Spec(Name::new(name.trim())));
+            i += len + 1;
+        } else if c.is_ascii_digit() || *c == '.' {
+            let mut word = String::new();
+            while let Some(c) = chars.get(i) {
+                let exp_sign =
+                    (*c == '-' || *c == '+') && word.ends_with(|p: char| p == 'e' || p == 'E');
+                if c.is_ascii_digit() || *c == '.' || *c == 'e' || *c == 'E' || exp_sign {
+                    word.push(*c);
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens
+                .push(Token::Num(word.parse().map_err(|_| {
+                    format!("Invalid number '{}' in: {}", word, expr)
+                })?));
+        } else if c.is_alphabetic() {
+            let mut word = String::new();
+            while let Some(c) = chars.get(i) {
+                if c.is_alphanumeric() || *c == '_' {
+                    word.push(*c);
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Word(word));
+        } else {
+            tokens.push(Token::Sym(*c));
+            i += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Collect a sequence of species concentration tokens.
+fn species(tokens: &[Token]) -> Option<Vec<Name>> {
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::Spec(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse a mass-action style product of a constant and species concentrations.
+fn product(tokens: &[Token]) -> Option<Rate> {
+    match tokens {
+        [Token::Num(k), Token::Word(w), rest @ ..] if w == "phi" => {
+            Some(Rate::Fluence(*k, species(rest)?))
+        }
+        [Token::Num(k), Token::Word(w), rest @ ..] if w == "A" => {
+            Some(Rate::Absorbed(*k, species(rest)?))
+        }
+        [Token::Num(k), rest @ ..] => {
+            let cs = species(rest)?;
+            Some(match cs.as_slice() {
+                [] => Rate::Zeroth(*k),
+                [a] => Rate::First(*k, a.clone()),
+                [a, b] => Rate::Second(*k, a.clone(), b.clone()),
+                [a, b, c] => Rate::Third(*k, a.clone(), b.clone(), c.clone()),
+                _ => Rate::Poly(*k, cs),
+            })
+        }
+        _ => None,
+    }
+}
+
+impl FromStr for Rate {
+    type Err = String;
+
+    /// Parse a rate expression written in the same form as it is displayed.
+    fn from_str(expr: &str) -> std::result::Result<Self, Self::Err> {
+        let tokens = tokenise(expr)?;
+
+        let rate = match tokens.as_slice() {
+            [Token::Num(v), Token::Spec(s), Token::Sym('/'), Token::Sym('('), Token::Num(km), Token::Sym('+'), Token::Spec(s2), Token::Sym(')')]
+                if s == s2 =>
+            {
+                Some(Self::MichaelisMenten(*v, *km, s.clone()))
+            }
+            [Token::Num(v), Token::Spec(s), Token::Sym('^'), Token::Num(n), Token::Sym('/'), Token::Sym('('), Token::Num(k), Token::Sym('^'), Token::Num(n2), Token::Sym('+'), Token::Spec(s2), Token::Sym('^'), Token::Num(n3), Token::Sym(')')]
+                if s == s2 && n == n2 && n == n3 =>
+            {
+                Some(Self::Hill(*v, *k, *n, s.clone()))
+            }
+            [Token::Num(v), Token::Spec(s), Token::Sym('/'), Token::Sym('('), Token::Num(km), Token::Sym('('), Token::Num(one), Token::Sym('+'), Token::Spec(i), Token::Sym('/'), Token::Num(ki), Token::Sym(')'), Token::Sym('+'), Token::Spec(s2), Token::Sym(')')]
+                if s == s2 && (*one - 1.0).abs() < std::f64::EPSILON =>
+            {
+                Some(Self::Inhibited(*v, *km, *ki, s.clone(), i.clone()))
+            }
+            [Token::Num(a), Token::Word(exp), Token::Sym('('), Token::Sym('-'), Token::Num(ea), Token::Sym('/'), Token::Word(rt), Token::Sym(')'), rest @ ..]
+                if exp == "exp" && rt == "RT" =>
+            {
+                species(rest).map(|cs| Self::Arrhenius(*a, *ea, cs))
+            }
+            _ => {
+                if let Some(split) = tokens.iter().position(|t| *t == Token::Sym('-')) {
+                    match (
+                        product(tokens.get(..split).expect("Invalid split.")),
+                        product(tokens.get(split + 1..).expect("Invalid split.")),
+                    ) {
+                        (Some(forward), Some(backward)) => {
+                            let (kf, fs) = forward.mass_action()?;
+                            let (kb, bs) = backward.mass_action()?;
+                            Some(Self::Reversible(kf, fs, kb, bs))
+                        }
+                        _ => None,
+                    }
+                } else {
+                    product(&tokens)
+                }
+            }
+        };
+
+        rate.ok_or_else(|| format!("Unable to parse rate expression: {}", expr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for expr in &[
+            "0.5",
+            "0.1 [ala]",
+            "2 [a] [b]",
+            "1 [a] [b] [c]",
+            "1 [a] [a] [b] [c]",
+            "0.001 phi [ppix] [o2]",
+            "3 A [ppix]",
+            "2 [s] / (0.5 + [s])",
+            "2 [s]^3 / (0.5^3 + [s]^3)",
+            "2 [s] / (0.5 (1 + [i] / 0.1) + [s])",
+            "1000 exp(-50000 / RT) [a]",
+            "1 [a] [b] - 0.5 [c]",
+        ] {
+            let rate: Rate = expr.parse().expect("Failed to parse rate.");
+            assert_eq!(rate.to_string(), *expr);
+
+            let reparsed: Rate = rate.to_string().parse().expect("Failed to reparse rate.");
+            assert_eq!(reparsed.to_string(), *expr);
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        for expr in &[
+            "",
+            "[a]",
+            "k [a]",
+            "1 [a",
+            "1 [a] / (0.5 + [b])",
+            "2 [s]^3 / (0.5^2 + [s]^3)",
+            "1 exp(-5 / RT",
+            "1 [a] - ",
+        ] {
+            assert!(expr.parse::<Rate>().is_err(), "Parsed: {}", expr);
         }
     }
 }
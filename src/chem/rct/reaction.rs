@@ -2,7 +2,10 @@
 
 use crate::{access, chem::Rate, dom::Name};
 use attr::json;
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+};
 
 /// Species reaction structure.
 #[json]
@@ -52,24 +55,176 @@ impl Reaction {
     }
 }
 
-impl Display for Reaction {
-    fn fmt(&self, fmt: &mut Formatter) -> Result {
-        if let Some((name, coeff)) = self.reactants.first() {
-            write!(fmt, "{}{}", coeff, name)?;
-            for (name, coeff) in self.reactants.iter().skip(1) {
-                write!(fmt, " + {}{}", coeff, name)?;
+impl FromStr for Reaction {
+    type Err = String;
+
+    /// Parse a chemical equation such as "8 ala -> ppix ; k = 0.1 [ala]".
+    /// Reversible reactions use "<=>" and may give mass-action rates as "; kf = 1.0, kb = 0.5".
+    fn from_str(eq: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parts = eq.splitn(2, ';');
+        let equation = format!(" {} ", parts.next().expect("Missing equation."));
+        let rate = parts
+            .next()
+            .ok_or_else(|| format!("Missing rate after ';' in: {}", eq))?;
+
+        let (reversible, split, arrow) = if let Some(split) = equation.find(" <=> ") {
+            (true, split, " <=> ")
+        } else if let Some(split) = equation.find(" -> ") {
+            (false, split, " -> ")
+        } else {
+            return Err(format!("Missing '->' or '<=>' in: {}", eq));
+        };
+        let reactants = terms(equation.get(..split).expect("Invalid split."))?;
+        let products = terms(equation.get(split + arrow.len()..).expect("Invalid split."))?;
+
+        let mut consts = Vec::new();
+        for assign in rate.split(',') {
+            let mut sides = assign.splitn(2, '=');
+            let key = sides.next().expect("Missing rate key.").trim();
+            let value = sides
+                .next()
+                .ok_or_else(|| format!("Missing '=' in rate: {}", assign))?
+                .trim();
+            consts.push((key, value));
+        }
+
+        let rate = match consts.as_slice() {
+            [("k", expr)] => expr.parse::<Rate>()?,
+            [("kf", kf), ("kb", kb)] if reversible => {
+                let parse = |k: &str| {
+                    k.parse::<f64>()
+                        .map_err(|_| format!("Invalid rate constant '{}' in: {}", k, eq))
+                };
+                let expand = |side: &[(Name, i32)]| -> Vec<Name> {
+                    side.iter()
+                        .flat_map(|(name, coeff)| vec![name.clone(); *coeff as usize])
+                        .collect()
+                };
+                Rate::Reversible(
+                    parse(kf)?,
+                    expand(&reactants),
+                    parse(kb)?,
+                    expand(&products),
+                )
             }
+            _ => return Err(format!("Invalid rate specification in: {}", eq)),
+        };
+
+        if reversible != matches!(rate, Rate::Reversible(..)) {
+            return Err(format!(
+                "Reaction direction does not match its rate in: {}",
+                eq
+            ));
         }
 
-        write!(fmt, " -> ").expect("Could not write to formatter.");
+        Ok(Self::new(reactants, products, rate))
+    }
+}
 
-        if let Some((name, coeff)) = self.products.first() {
-            write!(fmt, "{}{}", coeff, name)?;
-            for (name, coeff) in self.products.iter().skip(1) {
-                write!(fmt, " + {}{}", coeff, name)?;
-            }
+/// Parse one side of a chemical equation into species and their stoichiometric coefficients.
+/// Terms are separated by a whitespace delimited '+', so species names may themselves contain '+'.
+fn terms(side: &str) -> std::result::Result<Vec<(Name, i32)>, String> {
+    let words: Vec<&str> = side.split_whitespace().collect();
+    if words.is_empty() {
+        return Ok(vec![]);
+    }
+
+    words
+        .split(|word| *word == "+")
+        .map(|term| match term {
+            [name] => Ok((Name::new(name), 1)),
+            [coeff, name] => coeff
+                .parse()
+                .map(|coeff| (Name::new(name), coeff))
+                .map_err(|_| format!("Invalid stoichiometric coefficient: {}", coeff)),
+            _ => Err(format!("Invalid equation term: {}", term.join(" "))),
+        })
+        .collect()
+}
+
+impl Display for Reaction {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let side = |terms: &[(Name, i32)]| -> String {
+            terms
+                .iter()
+                .map(|(name, coeff)| {
+                    if *coeff == 1 {
+                        name.to_string()
+                    } else {
+                        format!("{} {}", coeff, name)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" + ")
+        };
+
+        let arrow = if let Rate::Reversible(..) = self.rate {
+            "<=>"
+        } else {
+            "->"
+        };
+        let equation = format!(
+            "{} {} {}",
+            side(&self.reactants),
+            arrow,
+            side(&self.products)
+        );
+
+        write!(fmt, "{} ; k = {}", equation.trim(), self.rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for eq in &[
+            "8 ala -> ppix ; k = 0.1 [ala]",
+            "ppix + o2 -> ppix + o- ; k = 0.001 phi [ppix] [o2]",
+            "2 a -> b ; k = 0.5 [a] [a]",
+            "a <=> b ; k = 1 [a] - 0.5 [b]",
+            "fe+ + o2 -> fe++ + o2- ; k = 1 [fe+] [o2]",
+        ] {
+            let react: Reaction = eq.parse().expect("Failed to parse reaction.");
+            let shown = react.to_string();
+
+            let reparsed: Reaction = shown.parse().expect("Failed to reparse reaction.");
+            assert_eq!(reparsed.to_string(), shown);
+            assert_eq!(reparsed.req_species(), react.req_species());
         }
+    }
+
+    #[test]
+    fn charged_species() {
+        let react: Reaction = "fe+ + o2 -> fe++ + o2- ; k = 1 [fe+] [o2]"
+            .parse()
+            .expect("Failed to parse reaction.");
 
-        write!(fmt, "\t{:<32} ", format!("({})", self.rate))
+        assert_eq!(
+            react.reactants(),
+            &vec![(Name::new("fe+"), 1), (Name::new("o2"), 1)]
+        );
+        assert_eq!(
+            react.products(),
+            &vec![(Name::new("fe++"), 1), (Name::new("o2-"), 1)]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        for eq in &[
+            "",
+            "a -> b",
+            "a b ; k = 1 [a]",
+            "a -> b ; 1 [a]",
+            "a -> b ; k = [a]",
+            "a -> b ; kf = 1, kb = 0.5",
+            "x a -> b ; k = 1 [a]",
+            "a + + b -> c ; k = 1 [a] [b]",
+        ] {
+            assert!(eq.parse::<Reaction>().is_err(), "Parsed: {}", eq);
+        }
     }
 }
@@ -2,7 +2,11 @@
 
 use crate::access;
 use attr::json;
-use std::fmt::{Display, Formatter, Result};
+use physical_constants::BOLTZMANN_CONSTANT;
+use std::{
+    f64::consts::PI,
+    fmt::{Display, Formatter, Result},
+};
 
 /// Reactive species structure.
 #[json]
@@ -20,6 +24,18 @@ impl Species {
     pub const fn new(rad: Option<f64>) -> Self {
         Self { rad }
     }
+
+    /// Calculate the Stokes-Einstein diffusion coefficient within a medium of given viscosity and temperature.
+    /// Non-diffusive species return None. [m^2 s^-1]
+    #[inline]
+    #[must_use]
+    pub fn diff_coeff(&self, visc: f64, temp: f64) -> Option<f64> {
+        assert!(visc > 0.0);
+        assert!(temp > 0.0);
+
+        self.rad
+            .map(|rad| BOLTZMANN_CONSTANT * temp / (6.0 * PI * visc * rad))
+    }
 }
 
 impl Display for Species {
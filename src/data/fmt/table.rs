@@ -1 +1,96 @@
 //! Table implementation.
+
+use crate::{
+    access,
+    file::{Load, Save},
+};
+use std::{
+    fs::{read_to_string, File},
+    io::Write,
+    path::Path,
+};
+
+/// Column headed table of values.
+/// Saved in comma separated format.
+pub struct Table {
+    /// Column headings.
+    headings: Vec<String>,
+    /// Data rows.
+    rows: Vec<Vec<f64>>,
+}
+
+impl Table {
+    access!(headings, Vec<String>);
+    access!(rows, Vec<Vec<f64>>);
+
+    /// Construct a new empty instance.
+    #[inline]
+    #[must_use]
+    pub fn new(headings: Vec<String>) -> Self {
+        assert!(!headings.is_empty());
+
+        Self {
+            headings,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row of data.
+    #[inline]
+    pub fn push(&mut self, row: Vec<f64>) {
+        assert!(row.len() == self.headings.len());
+
+        self.rows.push(row);
+    }
+
+    /// Get the values of the column with the given heading, if present.
+    #[inline]
+    #[must_use]
+    pub fn column(&self, heading: &str) -> Option<Vec<f64>> {
+        self.headings.iter().position(|h| h == heading).map(|j| {
+            self.rows
+                .iter()
+                .map(|row| *row.get(j).expect("Missing column value."))
+                .collect()
+        })
+    }
+}
+
+impl Load for Table {
+    #[inline]
+    fn load(path: &Path) -> Self {
+        let text = read_to_string(path).expect("Unable to read table file.");
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+        let headings = lines
+            .next()
+            .expect("Table file is empty.")
+            .split(',')
+            .map(|h| h.trim().to_string())
+            .collect();
+        let mut table = Self::new(headings);
+
+        for line in lines {
+            table.push(
+                line.split(',')
+                    .map(|x| x.trim().parse().expect("Unable to parse table value."))
+                    .collect(),
+            );
+        }
+
+        table
+    }
+}
+
+impl Save for Table {
+    #[inline]
+    fn save(&self, path: &Path) {
+        let mut file = File::create(path).expect("Unable to create table file.");
+
+        writeln!(file, "{}", self.headings.join(",")).expect("Failed to write to file.");
+        for row in &self.rows {
+            let values: Vec<String> = row.iter().map(ToString::to_string).collect();
+            writeln!(file, "{}", values.join(",")).expect("Failed to write to file.");
+        }
+    }
+}
@@ -18,6 +18,8 @@ pub struct Cell<'a> {
     inter_tris: Vec<((&'a Name, &'a Interface), Vec<&'a SmoothTriangle>)>,
     /// Local chemical state.
     state: State,
+    /// Optional local fibre axis.
+    fibre: Option<Unit<Vector3<f64>>>,
 }
 
 impl<'a> Cell<'a> {
@@ -28,6 +30,7 @@ impl<'a> Cell<'a> {
         Vec<((&'a Name, &'a Interface), Vec<&'a SmoothTriangle>)>
     );
     access!(state, state_mut, State);
+    access!(fibre, Option<Unit<Vector3<f64>>>);
 
     /// Construct a new instance.
     #[inline]
@@ -38,6 +41,7 @@ impl<'a> Cell<'a> {
         inters: &'a Set<Interface>,
         meshes: &'a Set<Mesh>,
         state: State,
+        fibre: Option<Unit<Vector3<f64>>>,
     ) -> Self {
         let mut inter_tris = Vec::new();
 
@@ -60,6 +64,7 @@ impl<'a> Cell<'a> {
             mat,
             inter_tris,
             state,
+            fibre,
         }
     }
 
@@ -83,20 +88,20 @@ impl<'a> Cell<'a> {
         nearest
     }
 
-    /// Determine the distance to an interface contained within the cell, if hitting on the inside of the interface, and the normal at the intersection point.
+    /// Determine the distance to an interface contained within the cell, if hitting on the inside of the interface, the normal at the intersection point, and the interface itself.
     #[inline]
     #[must_use]
     pub fn inter_dist_inside_norm_inter(
         &self,
         ray: &Ray,
-    ) -> Option<(f64, bool, Unit<Vector3<f64>>, &Interface)> {
-        let mut nearest: Option<(f64, bool, Unit<Vector3<f64>>, &Interface)> = None;
+    ) -> Option<(f64, bool, Unit<Vector3<f64>>, (&Name, &Interface))> {
+        let mut nearest: Option<(f64, bool, Unit<Vector3<f64>>, (&Name, &Interface))> = None;
 
-        for ((_name, inter), tris) in &self.inter_tris {
+        for ((name, inter), tris) in &self.inter_tris {
             for tri in tris {
                 if let Some((dist, inside, norm)) = tri.dist_inside_norm(ray) {
                     if nearest.is_none() || dist < nearest.expect("Something went wrong...").0 {
-                        nearest = Some((dist, inside, norm, inter));
+                        nearest = Some((dist, inside, norm, (name, inter)));
                     }
                 }
             }
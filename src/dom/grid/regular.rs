@@ -6,11 +6,12 @@ use crate::{
     dom::{index_of_name, observe_mat, Cell, Name, Set},
     geom::{Aabb, Ray},
     math::min,
+    phys::Orient,
     uni::{Material, State, Verse},
     util::bar,
 };
-use nalgebra::{Point3, Unit};
-use ndarray::Array3;
+use nalgebra::{Point3, Unit, Vector3};
+use ndarray::{s, Array3, Array4};
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter, Result},
@@ -29,12 +30,18 @@ pub struct Regular<'a> {
 
 impl<'a> Regular<'a> {
     access!(bound, Aabb);
-    access!(cells, Array3<Cell<'a>>);
+    access!(cells, cells_mut, Array3<Cell<'a>>);
 
     /// Construct a new instance.
+    /// Fibre orientation maps of the materials whose fibres are orientated by a map must be given.
     #[inline]
     #[must_use]
-    pub fn new(bound: Aabb, shape: [usize; 3], verse: &'a Verse) -> Self {
+    pub fn new(
+        bound: Aabb,
+        shape: [usize; 3],
+        verse: &'a Verse,
+        fibre_maps: &Set<Array4<f64>>,
+    ) -> Self {
         println!("Building regular grid...");
 
         let mut cell_size = bound.widths();
@@ -63,20 +70,28 @@ impl<'a> Regular<'a> {
             panic!("Unable to determine suitable tracing ray.");
         };
 
-        let total_cells = shape[0] * shape[1] * shape[2];
+        let res_x = *shape.get(0).expect("Missing resolution.");
+        let res_y = *shape.get(1).expect("Missing resolution.");
+        let res_z = *shape.get(2).expect("Missing resolution.");
+
+        for map in fibre_maps.map().values() {
+            assert!(map.shape() == [res_x, res_y, res_z, 3]);
+        }
+
+        let total_cells = res_x * res_y * res_z;
         let mut cells = Vec::with_capacity(total_cells);
         let pb = bar("Building grid", total_cells as u64);
-        for xi in 0..*shape.get(0).expect("Missing resolution.") {
+        for xi in 0..res_x {
             let x = cell_size
                 .get(0)
                 .expect("Missing resolution.")
                 .mul_add(xi as f64, bound.mins().x);
-            for yi in 0..*shape.get(1).expect("Missing resolution.") {
+            for yi in 0..res_y {
                 let y = cell_size
                     .get(1)
                     .expect("Missing resolution.")
                     .mul_add(yi as f64, bound.mins().y);
-                for zi in 0..*shape.get(2).expect("Missing resolution.") {
+                for zi in 0..res_z {
                     pb.inc(1);
 
                     let z = cell_size
@@ -98,24 +113,40 @@ impl<'a> Regular<'a> {
                     )
                     .expect("Unable to observe material.");
 
-                    let init_state = if let Some(init_state) = verse
+                    let material = verse
                         .mats()
                         .map()
                         .get(&mat)
-                        .expect("Invalid material name.")
-                        .init_state()
-                    {
+                        .expect("Invalid material name.");
+
+                    let init_state = if let Some(init_state) = material.init_state() {
                         init_state.build(verse.specs())
                     } else {
                         State::empty(verse.specs().map().len())
                     };
 
+                    let fibre = material
+                        .fibres()
+                        .as_ref()
+                        .map(|fibres| match fibres.orient() {
+                            Orient::Constant(axis) => Unit::new_normalize(*axis),
+                            Orient::Map(_path) => {
+                                let map = fibre_maps.map().get(&mat).expect("Missing fibre map.");
+                                Unit::new_normalize(Vector3::new(
+                                    *map.get([xi, yi, zi, 0]).expect("Invalid fibre map index."),
+                                    *map.get([xi, yi, zi, 1]).expect("Invalid fibre map index."),
+                                    *map.get([xi, yi, zi, 2]).expect("Invalid fibre map index."),
+                                ))
+                            }
+                        });
+
                     cells.push(Cell::new(
                         cell_bound,
                         mat,
                         verse.inters(),
                         verse.meshes(),
                         init_state,
+                        fibre,
                     ));
                 }
             }
@@ -231,6 +262,37 @@ impl<'a> Regular<'a> {
         Set::new(set)
     }
 
+    /// Collect the concentrations of all species into a single array of shape [nx, ny, nz, num_specs].
+    #[inline]
+    #[must_use]
+    pub fn concs(&self) -> Array4<f64> {
+        let [nx, ny, nz] = self.res();
+        let num_specs = self
+            .cells
+            .iter()
+            .next()
+            .map_or(0, |c| c.state().concs().len());
+
+        let mut concs = Array4::zeros([nx, ny, nz, num_specs]);
+        for ((xi, yi, zi), cell) in self.cells.indexed_iter() {
+            concs
+                .slice_mut(s![xi, yi, zi, ..])
+                .assign(cell.state().concs());
+        }
+
+        concs
+    }
+
+    /// Overwrite the concentrations of all species from an array of shape [nx, ny, nz, num_specs].
+    #[inline]
+    pub fn set_concs(&mut self, concs: &Array4<f64>) {
+        for ((xi, yi, zi), cell) in self.cells.indexed_iter_mut() {
+            cell.state_mut()
+                .concs_mut()
+                .assign(&concs.slice(s![xi, yi, zi, ..]));
+        }
+    }
+
     /// Determine the cells containing intersecting interfaces.
     #[inline]
     #[must_use]
@@ -20,7 +20,7 @@ pub struct Set<T> {
 }
 
 impl<T> Set<T> {
-    access!(map, BTreeMap<Name, T>);
+    access!(map, map_mut, BTreeMap<Name, T>);
 
     /// Construct a new instance.
     #[inline]
@@ -0,0 +1,33 @@
+//! Fitting implementation.
+
+use crate::{access, dom::Name};
+use attr::json;
+
+/// Kinetic parameter fitting settings.
+#[json]
+pub struct Fitting {
+    /// Material whose initial state and reaction multiplier are used.
+    mat: Name,
+    /// Measured concentration time series files, relative to the input directory.
+    data: Vec<String>,
+    /// Fitted parameters: reaction name, index within the rate parameters, lower bound and upper bound.
+    params: Vec<(Name, usize, f64, f64)>,
+    /// Relative convergence tolerance.
+    tol: f64,
+    /// Maximum number of iterations.
+    max_iters: usize,
+    /// Optional fluence rate. [W m^-2]
+    fluence_rate: Option<f64>,
+    /// Optional temperature. [K]
+    temp: Option<f64>,
+}
+
+impl Fitting {
+    access!(mat, Name);
+    access!(data, Vec<String>);
+    access!(params, Vec<(Name, usize, f64, f64)>);
+    access!(tol, f64);
+    access!(max_iters, usize);
+    access!(fluence_rate, Option<f64>);
+    access!(temp, Option<f64>);
+}
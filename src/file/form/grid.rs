@@ -1,8 +1,16 @@
 //! Grid implementation.
 
-use crate::{access, dom::Regular, uni::Verse};
+use crate::{
+    access,
+    dom::{Regular, Set},
+    file::from_json,
+    phys::Orient,
+    uni::Verse,
+};
 use attr::json;
 use nalgebra::Point3;
+use ndarray::Array4;
+use std::{collections::BTreeMap, path::Path};
 
 /// Grid construction form.
 #[json]
@@ -21,13 +29,25 @@ impl Grid {
     access!(maxs, Point3<f64>);
 
     /// Form a new grid instance.
+    /// Fibre orientation maps are loaded relative to the input directory.
     #[inline]
     #[must_use]
-    pub fn form<'a>(&self, verse: &'a Verse) -> Regular<'a> {
+    pub fn form<'a>(&self, in_dir: &Path, verse: &'a Verse) -> Regular<'a> {
+        let mut fibre_maps = BTreeMap::new();
+        for (name, mat) in verse.mats().map() {
+            if let Some(fibres) = mat.fibres() {
+                if let Orient::Map(path) = fibres.orient() {
+                    let map: Array4<f64> = from_json(&in_dir.join(path));
+                    fibre_maps.insert(name.clone(), map);
+                }
+            }
+        }
+
         Regular::new(
             crate::geom::Aabb::new(self.mins, self.maxs),
             self.res,
             verse,
+            &Set::new(fibre_maps),
         )
     }
 }
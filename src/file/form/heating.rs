@@ -0,0 +1,27 @@
+//! Heating implementation.
+
+use crate::{access, phys::Loss};
+use attr::json;
+
+/// Bioheat simulation settings.
+#[json]
+pub struct Heating {
+    /// Output times. [s]
+    times: Vec<f64>,
+    /// Optional illumination duration, after which the light is switched off. [s]
+    illum: Option<f64>,
+    /// Optional surface heat losses.
+    losses: Option<Vec<Loss>>,
+    /// Optional initial and arterial blood temperature. [K]
+    init_temp: Option<f64>,
+    /// Optional pain threshold temperature. [K]
+    pain: Option<f64>,
+}
+
+impl Heating {
+    access!(times, Vec<f64>);
+    access!(illum, Option<f64>);
+    access!(losses, Option<Vec<Loss>>);
+    access!(init_temp, Option<f64>);
+    access!(pain, Option<f64>);
+}
@@ -0,0 +1,47 @@
+//! Kinetics implementation.
+
+use crate::{
+    access,
+    chem::{FaceBound, Pharmacokinetics, Topical},
+    dom::Name,
+    file::Steady,
+};
+use attr::json;
+
+/// Kinetics simulation settings.
+#[json]
+pub struct Kinetics {
+    /// Output times. [s]
+    times: Vec<f64>,
+    /// Integration error tolerance.
+    tol: f64,
+    /// Optional diffusion temperature, diffusion is disabled when absent. [K]
+    temp: Option<f64>,
+    /// Optional concentration snapshot times. [s]
+    snaps: Option<Vec<f64>>,
+    /// Optional light re-simulation times, coupling the light field to the chemistry. [s]
+    cycles: Option<Vec<f64>>,
+    /// Optional species boundary conditions on the grid faces, other faces are closed.
+    faces: Option<Vec<FaceBound>>,
+    /// Optional hypoxia species and concentration threshold, hypoxia maps are saved at the snapshot times.
+    hypoxia: Option<(Name, f64)>,
+    /// Optional topically applied creams.
+    topicals: Option<Vec<Topical>>,
+    /// Optional systemic administration.
+    pk: Option<Pharmacokinetics>,
+    /// Optional steady-state solver, solving for the equilibrium field instead of time-stepping.
+    steady: Option<Steady>,
+}
+
+impl Kinetics {
+    access!(times, Vec<f64>);
+    access!(tol, f64);
+    access!(temp, Option<f64>);
+    access!(snaps, Option<Vec<f64>>);
+    access!(cycles, Option<Vec<f64>>);
+    access!(faces, Option<Vec<FaceBound>>);
+    access!(hypoxia, Option<(Name, f64)>);
+    access!(topicals, Option<Vec<Topical>>);
+    access!(pk, Option<Pharmacokinetics>);
+    access!(steady, Option<Steady>);
+}
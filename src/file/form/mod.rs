@@ -1,6 +1,11 @@
 //! Form sub-module.
 
+pub mod fitting;
 pub mod grid;
+pub mod heating;
+pub mod kinetics;
+pub mod steady;
+pub mod stochastic;
 pub mod verse;
 
-pub use self::{grid::*, verse::*};
+pub use self::{fitting::*, grid::*, heating::*, kinetics::*, steady::*, stochastic::*, verse::*};
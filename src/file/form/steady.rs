@@ -0,0 +1,24 @@
+//! Steady implementation.
+
+use crate::access;
+use attr::json;
+
+/// Steady-state solver settings.
+#[json]
+pub struct Steady {
+    /// Relative residual tolerance.
+    tol: f64,
+    /// Maximum number of pseudo-time steps.
+    max_steps: usize,
+    /// Initial pseudo-time step. [s]
+    init_step: f64,
+    /// Optional Krylov subspace dimension before restarting.
+    krylov: Option<usize>,
+}
+
+impl Steady {
+    access!(tol, f64);
+    access!(max_steps, usize);
+    access!(init_step, f64);
+    access!(krylov, Option<usize>);
+}
@@ -0,0 +1,33 @@
+//! Stochastic implementation.
+
+use crate::{access, chem::Method, dom::Name};
+use attr::json;
+
+/// Stochastic kinetics simulation settings.
+#[json]
+pub struct Stochastic {
+    /// Material whose initial state and reaction multiplier are simulated.
+    mat: Name,
+    /// Output times. [s]
+    times: Vec<f64>,
+    /// Number of ensemble realisations.
+    runs: usize,
+    /// System size, the number of molecules per unit concentration.
+    size: f64,
+    /// Simulation method.
+    method: Method,
+    /// Optional fluence rate. [W m^-2]
+    fluence_rate: Option<f64>,
+    /// Optional temperature. [K]
+    temp: Option<f64>,
+}
+
+impl Stochastic {
+    access!(mat, Name);
+    access!(times, Vec<f64>);
+    access!(runs, usize);
+    access!(size, f64);
+    access!(method, Method);
+    access!(fluence_rate, Option<f64>);
+    access!(temp, Option<f64>);
+}
@@ -1,11 +1,13 @@
 //! Verse implementation.
 
 use crate::{
-    chem::{Reaction, Species},
+    chem::{Analysis, Species},
     dom::{load_set, load_surfs, Name, Set},
+    file::Reaction,
     uni::{Interface, Light, Material, Verse as UniVerse},
 };
 use attr::json;
+use log::{info, warn};
 use std::{collections::BTreeMap, path::Path};
 
 /// Verse construction form.
@@ -21,11 +23,13 @@ pub struct Verse {
 
 impl Verse {
     /// Form a new instance.
+    /// The reaction network is analysed and rejected if invalid.
+    /// Material chromophores are resolved against the species set.
     #[inline]
     #[must_use]
     pub fn form(self, in_dir: &Path) -> UniVerse {
         let mat_list = self.mat_list();
-        let mats = load_set::<Material>(&in_dir.join("mats"), &mat_list, "json");
+        let mut mats = load_set::<Material>(&in_dir.join("mats"), &mat_list, "json");
 
         let surf_list = self.surf_list();
         let meshes = load_surfs(
@@ -38,9 +42,29 @@ impl Verse {
 
         let spec_list = self.spec_list();
         let specs = load_set::<Species>(&in_dir.join("specs"), &spec_list, "json");
+        for mat in mats.map_mut().values_mut() {
+            mat.resolve(&specs);
+        }
 
         let inters = Set::new(self.inters);
-        let reacts = Set::new(self.reacts);
+        let reacts = Set::new(
+            self.reacts
+                .into_iter()
+                .map(|(name, react)| (name, react.build()))
+                .collect(),
+        );
+
+        let analysis = Analysis::new(&reacts, &specs);
+        info!("Reaction network analysis:\n{}", analysis);
+        for warning in analysis.warnings() {
+            warn!("{}", warning);
+        }
+        if !analysis.errors().is_empty() {
+            panic!(
+                "Invalid reaction network:\n\t{}",
+                analysis.errors().join("\n\t")
+            );
+        }
 
         let lights = Set::new(self.lights);
 
@@ -1,7 +1,8 @@
 //! Json sub-module.
 
+pub mod reaction;
 pub mod state;
 pub mod surface;
 pub mod transform;
 
-pub use self::{state::*, surface::*, transform::*};
+pub use self::{reaction::*, state::*, surface::*, transform::*};
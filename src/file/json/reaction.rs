@@ -0,0 +1,41 @@
+//! Reaction implementation.
+
+use crate::{chem::Reaction as ChemReaction, dom::Name};
+use attr::json;
+
+/// Reaction construction structure.
+#[json]
+#[serde(untagged)]
+pub enum Reaction {
+    /// Chemical equation string. "8 ala -> ppix ; k = 0.1 [ala]"
+    Equation(String),
+    /// Explicit reactants, products and rate.
+    Explicit(ChemReaction),
+}
+
+impl Reaction {
+    /// Build a reaction.
+    #[inline]
+    #[must_use]
+    pub fn build(self) -> ChemReaction {
+        match self {
+            Self::Equation(eq) => eq
+                .parse()
+                .unwrap_or_else(|err| panic!("Invalid reaction equation: {}", err)),
+            Self::Explicit(react) => react,
+        }
+    }
+
+    /// Get a list of all species required for the reaction.
+    #[inline]
+    #[must_use]
+    pub fn req_species(&self) -> Vec<Name> {
+        match self {
+            Self::Equation(eq) => eq
+                .parse::<ChemReaction>()
+                .unwrap_or_else(|err| panic!("Invalid reaction equation: {}", err))
+                .req_species(),
+            Self::Explicit(react) => react.req_species(),
+        }
+    }
+}
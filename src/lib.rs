@@ -29,6 +29,7 @@
     clippy::wildcard_enum_match_arm
 )]
 
+pub mod bio;
 pub mod chem;
 pub mod data;
 pub mod dom;
@@ -31,6 +31,13 @@ pub enum Formula {
         /// Recovery rate.
         r: f64,
     },
+    /// Tabulated formula. = linear interpolation of ys over xs
+    Tabulated {
+        /// Ascending input values.
+        xs: Vec<f64>,
+        /// Corresponding output values.
+        ys: Vec<f64>,
+    },
 }
 
 impl Formula {
@@ -50,6 +57,35 @@ impl Formula {
                 sum
             }
             Self::Recovery { c, r } => (c - x) * r,
+            Self::Tabulated { xs, ys } => interpolate(xs, ys, x),
         }
     }
 }
+
+/// Linearly interpolate a table of values, clamping to the end values outside the tabulated range.
+/// The input values must be strictly ascending.
+#[inline]
+#[must_use]
+pub fn interpolate(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    assert!(!xs.is_empty());
+    assert!(xs.len() == ys.len());
+    assert!(
+        xs.iter().zip(xs.iter().skip(1)).all(|(x0, x1)| x0 < x1),
+        "Tabulated input values must be strictly ascending."
+    );
+
+    let first = *xs.first().expect("Missing first value.");
+    if x <= first {
+        return *ys.first().expect("Missing first value.");
+    }
+
+    for (i, (x1, y1)) in xs.iter().zip(ys).enumerate().skip(1) {
+        if x <= *x1 {
+            let x0 = *xs.get(i - 1).expect("Invalid index.");
+            let y0 = *ys.get(i - 1).expect("Invalid index.");
+            return y0 + ((y1 - y0) * (x - x0) / (x1 - x0));
+        }
+    }
+
+    *ys.last().expect("Missing last value.")
+}
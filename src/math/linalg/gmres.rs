@@ -0,0 +1,163 @@
+//! Generalised minimal residual functions.
+
+use ndarray::Array1;
+
+/// Calculate the Euclidean norm of a vector.
+#[inline]
+#[must_use]
+pub fn norm(v: &Array1<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+/// Solve the linear system A x = b with restarted GMRES, given the action of A on a vector.
+/// The initial value of x is used as the starting guess.
+/// Returns the number of iterations taken and the final relative residual.
+#[inline]
+pub fn gmres<A>(
+    a: A,
+    b: &Array1<f64>,
+    x: &mut Array1<f64>,
+    restart: usize,
+    tol: f64,
+    max_iters: usize,
+) -> (usize, f64)
+where
+    A: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    assert!(restart > 0);
+    assert!(tol > 0.0);
+
+    let b_norm = norm(b);
+    if b_norm <= 0.0 {
+        x.fill(0.0);
+        return (0, 0.0);
+    }
+
+    let mut iters = 0;
+    loop {
+        let r = b - &a(x);
+        let beta = norm(&r);
+        if beta / b_norm <= tol || iters >= max_iters {
+            return (iters, beta / b_norm);
+        }
+
+        let mut vs = vec![r / beta];
+        let mut rs: Vec<Vec<f64>> = Vec::with_capacity(restart);
+        let mut rots: Vec<(f64, f64)> = Vec::with_capacity(restart);
+        let mut g = vec![beta];
+
+        while rs.len() < restart && iters < max_iters {
+            let mut w = a(vs.last().expect("Missing Krylov vector."));
+            let col: Vec<f64> = vs
+                .iter()
+                .map(|v| {
+                    let h = w.dot(v);
+                    w.scaled_add(-h, v);
+                    h
+                })
+                .collect();
+            let w_norm = norm(&w);
+
+            rs.push(rotate(&col, &mut rots, w_norm));
+
+            let (c, s) = *rots.last().expect("Missing rotation.");
+            let g_k = g.pop().expect("Missing residual.");
+            g.push(c * g_k);
+            g.push(-s * g_k);
+
+            iters += 1;
+
+            if w_norm <= 0.0 || g.last().expect("Missing residual.").abs() / b_norm <= tol {
+                break;
+            }
+            vs.push(w / w_norm);
+        }
+
+        g.pop();
+        for (yi, v) in back_substitute(&rs, g).iter().zip(&vs) {
+            x.scaled_add(*yi, v);
+        }
+    }
+}
+
+/// Apply the previous Givens rotations to a new Hessenberg column, then form and record the rotation annihilating its subdiagonal.
+/// Returns the corresponding column of the upper triangular factor.
+#[inline]
+#[must_use]
+fn rotate(col: &[f64], rots: &mut Vec<(f64, f64)>, sub: f64) -> Vec<f64> {
+    let mut entries = col.iter();
+    let mut cur = *entries.next().expect("Missing Hessenberg entry.");
+
+    let mut r = Vec::with_capacity(col.len());
+    for ((c, s), next) in rots.iter().zip(entries) {
+        r.push(c.mul_add(cur, s * next));
+        cur = (-s).mul_add(cur, c * next);
+    }
+
+    let denom = cur.hypot(sub);
+    if denom > 0.0 {
+        rots.push((cur / denom, sub / denom));
+    } else {
+        rots.push((1.0, 0.0));
+    }
+    r.push(denom);
+
+    r
+}
+
+/// Solve the upper triangular system R y = g, given the columns of R.
+/// Singular diagonal entries give a zero component.
+#[inline]
+#[must_use]
+fn back_substitute(rs: &[Vec<f64>], mut g: Vec<f64>) -> Vec<f64> {
+    let mut y = vec![0.0; rs.len()];
+    for (col, yi) in rs.iter().zip(y.iter_mut()).rev() {
+        let (diag, upper) = col.split_last().expect("Missing diagonal entry.");
+        let gi = g.pop().expect("Missing residual.");
+
+        *yi = if diag.abs() > 0.0 { gi / diag } else { 0.0 };
+        for (gj, h) in g.iter_mut().zip(upper) {
+            *gj -= h * *yi;
+        }
+    }
+
+    y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array2};
+
+    /// Solve a dense system with GMRES and check the residual.
+    fn check(a: &Array2<f64>, b: &Array1<f64>, restart: usize) {
+        let mut x = Array1::zeros(b.len());
+        let (_iters, res) = gmres(|v| a.dot(v), b, &mut x, restart, 1.0e-12, 100);
+
+        assert!(res <= 1.0e-12);
+        assert!(norm(&(b - &a.dot(&x))) <= 1.0e-10 * norm(b));
+    }
+
+    #[test]
+    fn symmetric_positive_definite() {
+        let a = array![[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]];
+        let b = array![1.0, 2.0, 3.0];
+
+        check(&a, &b, 3);
+        check(&a, &b, 1);
+    }
+
+    #[test]
+    fn non_symmetric() {
+        let a = array![
+            [3.0, -1.0, 0.0, 2.0],
+            [1.0, 4.0, -2.0, 0.0],
+            [0.0, 2.0, 5.0, -1.0],
+            [-1.0, 0.0, 1.0, 3.0]
+        ];
+        let b = array![1.0, -2.0, 0.5, 4.0];
+
+        check(&a, &b, 4);
+        check(&a, &b, 2);
+    }
+}
@@ -0,0 +1,6 @@
+//! Linear algebra sub-module.
+
+pub mod gmres;
+pub mod null;
+
+pub use self::{gmres::*, null::*};
@@ -0,0 +1,91 @@
+//! Null space functions.
+
+use ndarray::{Array1, Array2};
+
+/// Magnitude below which reduced matrix elements are treated as zero.
+const PIVOT_TOL: f64 = 1.0e-9;
+
+/// Determine a basis of the null space of a matrix, the vectors x for which A x = 0.
+/// Uses reduction to row echelon form, so each basis vector has a unit coefficient at one of the free columns.
+#[inline]
+#[must_use]
+pub fn null_space(mat: &Array2<f64>) -> Vec<Array1<f64>> {
+    let (rows, cols) = mat.dim();
+    let mut rref = mat.clone();
+
+    let mut pivots = Vec::new();
+    let mut row = 0;
+    for col in 0..cols {
+        if row >= rows {
+            break;
+        }
+
+        let (best, max) = rref
+            .column(col)
+            .iter()
+            .enumerate()
+            .skip(row)
+            .map(|(r, v)| (r, v.abs()))
+            .fold((row, 0.0), |a, b| if b.1 > a.1 { b } else { a });
+        if max < PIVOT_TOL {
+            continue;
+        }
+
+        for c in 0..cols {
+            rref.swap([row, c], [best, c]);
+        }
+        let pivot = *rref.get([row, col]).expect("Invalid pivot index.");
+        let pivot_row = rref.row(row).mapv(|v| v / pivot);
+        for (r, mut other) in rref.outer_iter_mut().enumerate() {
+            if r == row {
+                other.assign(&pivot_row);
+                continue;
+            }
+            let factor = *other.get(col).expect("Invalid column index.");
+            if factor.abs() > 0.0 {
+                other.scaled_add(-factor, &pivot_row);
+            }
+        }
+
+        pivots.push(col);
+        row += 1;
+    }
+
+    (0..cols)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut x = Array1::zeros(cols);
+            *x.get_mut(free).expect("Invalid column index.") = 1.0;
+            for (r, pivot) in pivots.iter().enumerate() {
+                *x.get_mut(*pivot).expect("Invalid column index.") =
+                    -*rref.get([r, free]).expect("Invalid reduced index.");
+            }
+            x
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn conservation_laws() {
+        // A + B -> C, with stoichiometry rows of (A, B, C).
+        let stoich: Array2<f64> = array![[-1.0, -1.0, 1.0]];
+        let laws = null_space(&stoich);
+        assert_eq!(laws.len(), 2);
+        for law in &laws {
+            assert!(stoich.dot(law).iter().all(|v| v.abs() < 1.0e-12));
+        }
+        assert!(laws.contains(&array![1.0, 0.0, 1.0]));
+
+        // A -> B, B -> C conserves the total.
+        let stoich: Array2<f64> = array![[-1.0, 1.0, 0.0], [0.0, -1.0, 1.0]];
+        assert_eq!(null_space(&stoich), vec![array![1.0, 1.0, 1.0]]);
+
+        // A full rank matrix has no null space.
+        assert!(null_space(&Array2::eye(3)).is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 //! Mathematics module.
 
 pub mod lambda;
+pub mod linalg;
+pub mod ode;
 pub mod rng;
 pub mod stat;
 pub mod tool;
 
-pub use self::{lambda::*, rng::*, stat::*, tool::*};
+pub use self::{lambda::*, linalg::*, ode::*, rng::*, stat::*, tool::*};
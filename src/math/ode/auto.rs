@@ -0,0 +1,49 @@
+//! Automatic stiffness switching integration functions.
+
+use crate::math::{rk45, ros23};
+use ndarray::{Array1, Array2};
+
+/// Extent of the explicit Dormand-Prince stability region along the negative real axis.
+const EXPLICIT_STABILITY: f64 = 3.3;
+
+/// Number of stability limited explicit steps, over a single integration, above which a system is treated as stiff.
+const STIFF_STEPS: f64 = 100.0;
+
+/// Determine an upper bound of the spectral radius of a Jacobian using the Gershgorin circle theorem.
+#[inline]
+#[must_use]
+pub fn spectral_bound(jac: &Array2<f64>) -> f64 {
+    jac.outer_iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+/// Determine if a system, with a given Jacobian, is stiff over a given duration.
+#[inline]
+#[must_use]
+pub fn is_stiff(jac: &Array2<f64>, duration: f64) -> bool {
+    spectral_bound(jac) * duration / EXPLICIT_STABILITY > STIFF_STEPS
+}
+
+/// Integrate a system of ordinary differential equations over a given duration.
+/// Stiff systems are integrated implicitly, otherwise explicitly.
+/// Returns the number of accepted steps.
+#[inline]
+pub fn integrate<F, J>(
+    f: F,
+    jac: J,
+    y: &mut Array1<f64>,
+    duration: f64,
+    dt: &mut f64,
+    tol: f64,
+) -> usize
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+    J: Fn(&Array1<f64>) -> Array2<f64>,
+{
+    if is_stiff(&jac(y), duration) {
+        ros23(f, jac, y, duration, dt, tol)
+    } else {
+        rk45(f, y, duration, dt, tol)
+    }
+}
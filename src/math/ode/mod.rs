@@ -0,0 +1,7 @@
+//! Ordinary differential equation sub-module.
+
+pub mod auto;
+pub mod rk45;
+pub mod rosenbrock;
+
+pub use self::{auto::*, rk45::*, rosenbrock::*};
@@ -0,0 +1,120 @@
+//! Dormand-Prince integration functions.
+
+use ndarray::Array1;
+
+/// Safety factor applied to step size predictions.
+const SAFETY: f64 = 0.9;
+
+/// Minimum step size scaling between steps.
+const MIN_SCALE: f64 = 0.2;
+
+/// Maximum step size scaling between steps.
+const MAX_SCALE: f64 = 5.0;
+
+/// Integrate a system of ordinary differential equations over a given duration using the adaptive Dormand-Prince method.
+/// The step size is updated in place so that it may be reused by subsequent calls.
+/// Returns the number of accepted steps.
+#[inline]
+pub fn rk45<F>(f: F, y: &mut Array1<f64>, duration: f64, dt: &mut f64, tol: f64) -> usize
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    assert!(duration >= 0.0);
+    assert!(tol > 0.0);
+
+    if *dt <= 0.0 || *dt > duration {
+        *dt = duration;
+    }
+
+    let mut steps = 0;
+    let mut t = 0.0;
+    while t < duration {
+        let h = dt.min(duration - t);
+
+        let k1 = f(y);
+        let k2 = f(&(&*y + &(&k1 * (h / 5.0))));
+        let k3 = f(&(&*y + &((&k1 * (3.0 / 40.0) + &k2 * (9.0 / 40.0)) * h)));
+        let k4 = f(&(&*y
+            + &((&k1 * (44.0 / 45.0) - &k2 * (56.0 / 15.0) + &k3 * (32.0 / 9.0)) * h)));
+        let k5 = f(&(&*y
+            + &((&k1 * (19372.0 / 6561.0) - &k2 * (25360.0 / 2187.0)
+                + &k3 * (64448.0 / 6561.0)
+                - &k4 * (212.0 / 729.0))
+                * h)));
+        let k6 = f(&(&*y
+            + &((&k1 * (9017.0 / 3168.0) - &k2 * (355.0 / 33.0)
+                + &k3 * (46732.0 / 5247.0)
+                + &k4 * (49.0 / 176.0)
+                - &k5 * (5103.0 / 18656.0))
+                * h)));
+        let y_next = &*y
+            + &((&k1 * (35.0 / 384.0) + &k3 * (500.0 / 1113.0) + &k4 * (125.0 / 192.0)
+                - &k5 * (2187.0 / 6784.0)
+                + &k6 * (11.0 / 84.0))
+                * h);
+        let k7 = f(&y_next);
+
+        let err = (&k1 * (71.0 / 57600.0) - &k3 * (71.0 / 16695.0) + &k4 * (71.0 / 1920.0)
+            - &k5 * (17253.0 / 339_200.0)
+            + &k6 * (22.0 / 525.0)
+            - &k7 * (1.0 / 40.0))
+            * h;
+        let err_norm = err_norm(&err, y, &y_next, tol);
+
+        if err_norm <= 1.0 {
+            t += h;
+            *y = y_next;
+            steps += 1;
+        }
+
+        *dt = h * scale(err_norm, 5.0);
+        assert!(*dt > 0.0, "Step size underflow.");
+    }
+
+    steps
+}
+
+/// Determine the maximum scaled error component of a step.
+#[inline]
+#[must_use]
+pub fn err_norm(err: &Array1<f64>, y: &Array1<f64>, y_next: &Array1<f64>, tol: f64) -> f64 {
+    err.iter()
+        .zip(y.iter().zip(y_next.iter()))
+        .map(|(e, (a, b))| e.abs() / (tol * (1.0 + a.abs().max(b.abs()))))
+        .fold(0.0, f64::max)
+}
+
+/// Determine the step size scaling for a given scaled error and method order.
+#[inline]
+#[must_use]
+pub fn scale(err_norm: f64, order: f64) -> f64 {
+    if err_norm <= 0.0 {
+        return MAX_SCALE;
+    }
+
+    (SAFETY * err_norm.powf(-1.0 / order))
+        .max(MIN_SCALE)
+        .min(MAX_SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn exponential_decay() {
+        let k = 2.0;
+        let duration = 3.0;
+
+        let mut y = array![1.0, 5.0];
+        let mut dt = 0.0;
+        let steps = rk45(|y| y * -k, &mut y, duration, &mut dt, 1.0e-10);
+        assert!(steps > 0);
+
+        for (y, y0) in y.iter().zip(&[1.0, 5.0]) {
+            let exact = y0 * (-k * duration).exp();
+            assert!((y - exact).abs() < 1.0e-8 * y0);
+        }
+    }
+}
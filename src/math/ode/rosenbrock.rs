@@ -0,0 +1,105 @@
+//! Rosenbrock integration functions.
+
+use crate::math::{err_norm, scale};
+use nalgebra::{DMatrix, DVector};
+use ndarray::{Array1, Array2};
+
+/// Integrate a stiff system of ordinary differential equations over a given duration using the adaptive
+/// second order Rosenbrock method of Shampine (with embedded third order error estimate).
+/// The step size is updated in place so that it may be reused by subsequent calls.
+/// Returns the number of accepted steps.
+#[inline]
+pub fn ros23<F, J>(
+    f: F,
+    jac: J,
+    y: &mut Array1<f64>,
+    duration: f64,
+    dt: &mut f64,
+    tol: f64,
+) -> usize
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+    J: Fn(&Array1<f64>) -> Array2<f64>,
+{
+    assert!(duration >= 0.0);
+    assert!(tol > 0.0);
+
+    let d = 1.0 / (2.0 + 2.0_f64.sqrt());
+    let e32 = 6.0 + 2.0_f64.sqrt();
+
+    if *dt <= 0.0 || *dt > duration {
+        *dt = duration;
+    }
+
+    let mut steps = 0;
+    let mut t = 0.0;
+    while t < duration {
+        let h = dt.min(duration - t);
+
+        let j = jac(y);
+        let n = y.len();
+        let w = DMatrix::from_fn(n, n, |r, c| {
+            let identity = if r == c { 1.0 } else { 0.0 };
+            identity - (h * d * j.get([r, c]).expect("Invalid Jacobian index."))
+        })
+        .lu();
+        let solve = |b: &Array1<f64>| -> Array1<f64> {
+            w.solve(&DVector::from_iterator(n, b.iter().cloned()))
+                .expect("Singular Rosenbrock iteration matrix.")
+                .iter()
+                .cloned()
+                .collect()
+        };
+
+        let f0 = f(y);
+        let k1 = solve(&f0);
+        let f1 = f(&(&*y + &(&k1 * (0.5 * h))));
+        let k2 = solve(&(&f1 - &k1)) + &k1;
+        let y_next = &*y + &(&k2 * h);
+        let f2 = f(&y_next);
+        let k3 = solve(&(&f2 - &((&k2 - &f1) * e32) - &((&k1 - &f0) * 2.0)));
+
+        let err = (&k1 - &(&k2 * 2.0) + &k3) * (h / 6.0);
+        let err_norm = err_norm(&err, y, &y_next, tol);
+
+        if err_norm <= 1.0 {
+            t += h;
+            *y = y_next;
+            steps += 1;
+        }
+
+        *dt = h * scale(err_norm, 3.0);
+        assert!(*dt > 0.0, "Step size underflow.");
+    }
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn exponential_decay() {
+        let ks = array![1.0, 1.0e4];
+        let duration = 2.0;
+
+        let mut y = array![1.0, 1.0];
+        let mut dt = 0.0;
+        let steps = ros23(
+            |y| -(y * &ks),
+            |_y| Array2::from_diag(&-&ks),
+            &mut y,
+            duration,
+            &mut dt,
+            1.0e-8,
+        );
+        assert!(steps > 0);
+
+        for (y, k) in y.iter().zip(ks.iter()) {
+            let exact = (-k * duration).exp();
+            assert!((y - exact).abs() < 1.0e-6);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Distribution functions.
 
 use rand::{rngs::ThreadRng, Rng};
+use std::f64::consts::PI;
 
 /// Sample the Henyey-Greenstein phase function with a given asymmetry parameter.
 #[inline]
@@ -17,3 +18,160 @@ pub fn henyey_greenstein(rng: &mut ThreadRng, asym: f64) -> f64 {
         / (2.0 * asym))
         .acos()
 }
+
+/// Sample an isotropic scattering angle.
+#[inline]
+#[must_use]
+pub fn isotropic(rng: &mut ThreadRng) -> f64 {
+    rng.gen_range(-1.0_f64, 1.0).acos()
+}
+
+/// Sample the Rayleigh phase function.
+/// Inverts the cumulative distribution of (1 + mu^2) analytically.
+#[inline]
+#[must_use]
+pub fn rayleigh(rng: &mut ThreadRng) -> f64 {
+    let q = 4.0 - (8.0 * rng.gen_range(0.0_f64, 1.0));
+    let d = (q.powi(2) / 4.0 + 1.0).sqrt();
+
+    let mu = ((-q / 2.0) + d).cbrt() + ((-q / 2.0) - d).cbrt();
+
+    mu.max(-1.0).min(1.0).acos()
+}
+
+/// Sample the Reynolds-McCormick (Gegenbauer kernel) phase function with a given asymmetry and shape parameter.
+/// Reduces to the Henyey-Greenstein phase function when the shape parameter is one half.
+/// A vanishing shape parameter is sampled using the analytic limit of the inverse cumulative distribution.
+#[inline]
+#[must_use]
+pub fn reynolds_mccormick(rng: &mut ThreadRng, asym: f64, alpha: f64) -> f64 {
+    assert!(asym.abs() < 1.0);
+    assert!(alpha > -0.5);
+
+    if asym.abs() < 1.0e-6 {
+        return isotropic(rng);
+    }
+
+    if alpha.abs() < 1.0e-6 {
+        let xi = rng.gen_range(0.0_f64, 1.0);
+        let s = (1.0 + asym).powf(2.0 * (1.0 - xi)) * (1.0 - asym).powf(2.0 * xi);
+
+        return ((1.0 + asym.powi(2) - s) / (2.0 * asym))
+            .max(-1.0)
+            .min(1.0)
+            .acos();
+    }
+
+    let lower = (1.0 + asym).powf(-2.0 * alpha);
+    let upper = (1.0 - asym).powf(-2.0 * alpha);
+    let s = rng
+        .gen_range(0.0_f64, 1.0)
+        .mul_add(upper - lower, lower)
+        .powf(-1.0 / alpha);
+
+    ((1.0 + asym.powi(2) - s) / (2.0 * asym))
+        .max(-1.0)
+        .min(1.0)
+        .acos()
+}
+
+/// Sample a normal distribution with a given mean and standard deviation.
+/// Uses the Box-Muller transform.
+#[inline]
+#[must_use]
+pub fn normal(rng: &mut ThreadRng, mean: f64, std_dev: f64) -> f64 {
+    let u = 1.0 - rng.gen_range(0.0_f64, 1.0);
+    let v = rng.gen_range(0.0_f64, 1.0);
+
+    ((-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()).mul_add(std_dev, mean)
+}
+
+/// Sample a Poisson distribution with a given mean.
+/// Small means are sampled exactly by multiplication of uniform deviates, large means by the rounded normal approximation.
+#[inline]
+#[must_use]
+pub fn poisson(rng: &mut ThreadRng, mean: f64) -> f64 {
+    assert!(mean >= 0.0);
+
+    if mean > 30.0 {
+        return normal(rng, mean, mean.sqrt()).round().max(0.0);
+    }
+
+    let limit = (-mean).exp();
+    let mut count = 0.0;
+    let mut prod = rng.gen_range(0.0_f64, 1.0);
+    while prod > limit {
+        count += 1.0;
+        prod *= rng.gen_range(0.0_f64, 1.0);
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of angles drawn per sampler.
+    const SAMPLES: i32 = 100_000;
+
+    /// Check the mean cosine of the sampled angles is within five standard errors of the expected value.
+    fn check_mean_cos(mut sample: impl FnMut() -> f64, exact: f64) {
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for _ in 0..SAMPLES {
+            let mu = sample().cos();
+            sum += mu;
+            sum_sq += mu * mu;
+        }
+        let mean = sum / f64::from(SAMPLES);
+        let std_err = ((sum_sq / f64::from(SAMPLES) - mean.powi(2)) / f64::from(SAMPLES)).sqrt();
+
+        assert!((mean - exact).abs() < 5.0 * std_err);
+    }
+
+    /// Integrate the mean cosine of the Reynolds-McCormick phase function by the midpoint rule.
+    fn reynolds_mccormick_exact(asym: f64, alpha: f64) -> f64 {
+        let steps = 100_000;
+        let (mut norm, mut first) = (0.0, 0.0);
+        for i in 0..steps {
+            let mu = (2.0 * (f64::from(i) + 0.5) / f64::from(steps)) - 1.0;
+            let p = (1.0 + asym.powi(2) - (2.0 * asym * mu)).powf(-(alpha + 1.0));
+            norm += p;
+            first += mu * p;
+        }
+
+        first / norm
+    }
+
+    #[test]
+    fn isotropic_mean_cos() {
+        let mut rng = rand::thread_rng();
+        check_mean_cos(|| isotropic(&mut rng), 0.0);
+    }
+
+    #[test]
+    fn rayleigh_mean_cos() {
+        let mut rng = rand::thread_rng();
+        check_mean_cos(|| rayleigh(&mut rng), 0.0);
+    }
+
+    #[test]
+    fn henyey_greenstein_mean_cos() {
+        let mut rng = rand::thread_rng();
+        for g in &[-0.5, 0.0, 0.3, 0.9] {
+            check_mean_cos(|| henyey_greenstein(&mut rng, *g), *g);
+        }
+    }
+
+    #[test]
+    fn reynolds_mccormick_mean_cos() {
+        let mut rng = rand::thread_rng();
+        check_mean_cos(|| reynolds_mccormick(&mut rng, 0.6, 0.5), 0.6);
+        for (g, alpha) in &[(0.6, 0.0), (0.4, 1.5), (-0.3, 0.2)] {
+            check_mean_cos(
+                || reynolds_mccormick(&mut rng, *g, *alpha),
+                reynolds_mccormick_exact(*g, *alpha),
+            );
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Least squares fitting functions.
+
+use crate::access;
+use nalgebra::{DMatrix, DVector};
+use ndarray::{Array1, Array2};
+
+/// Initial Levenberg-Marquardt damping parameter.
+const INIT_DAMPING: f64 = 1.0e-3;
+/// Factor by which the damping parameter is changed after each trial step.
+const DAMPING_FACTOR: f64 = 10.0;
+/// Damping parameter beyond which no further progress is possible.
+const MAX_DAMPING: f64 = 1.0e16;
+
+/// Result of a least squares fit.
+pub struct Fit {
+    /// Fitted parameters.
+    params: Array1<f64>,
+    /// Residuals at the fitted parameters.
+    residuals: Array1<f64>,
+    /// Jacobian of the residuals with respect to the parameters at the fitted parameters. [residuals x params]
+    jacobian: Array2<f64>,
+    /// Number of iterations taken.
+    iters: usize,
+}
+
+impl Fit {
+    access!(params, Array1<f64>);
+    access!(residuals, Array1<f64>);
+    access!(jacobian, Array2<f64>);
+    access!(iters, usize);
+
+    /// Calculate the sum of the squared residuals.
+    #[inline]
+    #[must_use]
+    pub fn sum_sq(&self) -> f64 {
+        self.residuals.dot(&self.residuals)
+    }
+
+    /// Calculate the asymptotic covariance matrix of the fitted parameters, s^2 (J^T J)^-1.
+    /// Returns None if there are no more residuals than parameters, or the parameters are not identifiable.
+    #[inline]
+    #[must_use]
+    pub fn covariance(&self) -> Option<Array2<f64>> {
+        let (m, n) = self.jacobian.dim();
+        if m <= n {
+            return None;
+        }
+
+        let jtj = self.jacobian.t().dot(&self.jacobian);
+        let inv = DMatrix::from_fn(n, n, |r, c| *jtj.get([r, c]).expect("Invalid index."))
+            .try_inverse()?;
+        let var = self.sum_sq() / (m - n) as f64;
+
+        Some(Array2::from_shape_fn((n, n), |(r, c)| {
+            inv.get((r, c)).expect("Invalid index.") * var
+        }))
+    }
+
+    /// Calculate the standard error of each fitted parameter.
+    #[inline]
+    #[must_use]
+    pub fn std_errs(&self) -> Option<Array1<f64>> {
+        self.covariance()
+            .map(|cov| cov.diag().mapv(|v| v.max(0.0).sqrt()))
+    }
+}
+
+/// Minimise the sum of the squared residuals returned by f, subject to lower and upper bounds on the parameters,
+/// using the Levenberg-Marquardt method with trial steps projected onto the feasible region.
+/// The initial value of x is used as the starting guess, and is updated in place.
+/// The Jacobian is estimated by forward differences of the given size relative to each parameter,
+/// which should be well above the noise in the residuals.
+/// Steps are limited to half of the bounded range and taken inwards at the upper bounds,
+/// and parameters with equal lower and upper bounds are held fixed.
+#[inline]
+pub fn levenberg_marquardt<F>(
+    f: F,
+    x: &mut Array1<f64>,
+    lower: &Array1<f64>,
+    upper: &Array1<f64>,
+    rel_step: f64,
+    tol: f64,
+    max_iters: usize,
+) -> Fit
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let n = x.len();
+    assert!(lower.len() == n && upper.len() == n);
+    assert!(lower.iter().zip(upper.iter()).all(|(l, u)| l <= u));
+    assert!(rel_step > 0.0);
+    assert!(tol > 0.0);
+
+    let clamp = |p: &mut Array1<f64>| {
+        for ((v, l), u) in p.iter_mut().zip(lower.iter()).zip(upper.iter()) {
+            *v = v.max(*l).min(*u);
+        }
+    };
+    let jacobian = |p: &Array1<f64>, r: &Array1<f64>| {
+        let mut jac = Array2::zeros((r.len(), n));
+        for (i, ((v, l), u)) in p.iter().zip(lower.iter()).zip(upper.iter()).enumerate() {
+            if u - l <= 0.0 {
+                continue;
+            }
+
+            let mut h = (rel_step * if v.abs() > 0.0 { v.abs() } else { 1.0 }).min((u - l) / 2.0);
+            if v + h > *u {
+                h = -h;
+            }
+            let mut q = p.clone();
+            *q.get_mut(i).expect("Invalid parameter index.") += h;
+            jac.column_mut(i).assign(&((f(&q) - r) / h));
+        }
+        jac
+    };
+
+    clamp(x);
+    let mut r = f(x);
+    let mut cost = r.dot(&r);
+    let mut jac = jacobian(x, &r);
+    let mut damping = INIT_DAMPING;
+
+    let mut iters = 0;
+    while iters < max_iters {
+        iters += 1;
+
+        let jtj = jac.t().dot(&jac);
+        let grad = jac.t().dot(&r);
+        let scale: Array1<f64> = jtj.diag().mapv(|d| d.max(std::f64::EPSILON));
+
+        let mut accepted = false;
+        while damping < MAX_DAMPING {
+            let a = DMatrix::from_fn(n, n, |row, col| {
+                let v = *jtj.get([row, col]).expect("Invalid index.");
+                if row == col {
+                    v + (damping * scale.get(row).expect("Invalid index."))
+                } else {
+                    v
+                }
+            });
+            let b = DVector::from_iterator(n, grad.iter().map(|g| -g));
+            let step = match a.lu().solve(&b) {
+                Some(step) => step,
+                None => {
+                    damping *= DAMPING_FACTOR;
+                    continue;
+                }
+            };
+
+            let mut x_trial = &*x + &step.iter().cloned().collect::<Array1<f64>>();
+            clamp(&mut x_trial);
+            let r_trial = f(&x_trial);
+            let cost_trial = r_trial.dot(&r_trial);
+
+            if cost_trial < cost {
+                let change = (&x_trial - &*x)
+                    .iter()
+                    .zip(x.iter())
+                    .fold(0.0_f64, |m, (d, v)| m.max(d.abs() / (v.abs() + tol)));
+                let decrease = (cost - cost_trial) / cost.max(std::f64::MIN_POSITIVE);
+
+                *x = x_trial;
+                r = r_trial;
+                cost = cost_trial;
+                jac = jacobian(x, &r);
+                damping = (damping / DAMPING_FACTOR).max(std::f64::EPSILON);
+                accepted = true;
+
+                if change <= tol || decrease <= tol {
+                    return Fit {
+                        params: x.clone(),
+                        residuals: r,
+                        jacobian: jac,
+                        iters,
+                    };
+                }
+                break;
+            }
+
+            damping *= DAMPING_FACTOR;
+        }
+
+        if !accepted {
+            break;
+        }
+    }
+
+    Fit {
+        params: x.clone(),
+        residuals: r,
+        jacobian: jac,
+        iters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn recover_decay() {
+        let (amp, k) = (2.5, 0.4);
+        let times: Array1<f64> = (0..20).map(|i| f64::from(i) * 0.5).collect();
+        let data = times.mapv(|t| amp * (-k * t).exp());
+        let model = |p: &Array1<f64>| {
+            let (a, b) = (
+                p.get(0).expect("Missing parameter."),
+                p.get(1).expect("Missing parameter."),
+            );
+            times.mapv(|t| a * (-b * t).exp()) - &data
+        };
+
+        let mut x = array![1.0, 1.0];
+        let fit = levenberg_marquardt(
+            model,
+            &mut x,
+            &array![0.0, 0.0],
+            &array![10.0, 10.0],
+            1.0e-7,
+            1.0e-12,
+            100,
+        );
+        assert!((fit.params() - &array![amp, k])
+            .iter()
+            .all(|d| d.abs() < 1.0e-6));
+        assert!(fit.sum_sq() < 1.0e-12);
+
+        let mut x = array![amp, 1.0];
+        let fit = levenberg_marquardt(
+            model,
+            &mut x,
+            &array![amp, 0.0],
+            &array![amp, 10.0],
+            1.0e-7,
+            1.0e-12,
+            100,
+        );
+        assert!((fit.params() - &array![amp, k])
+            .iter()
+            .all(|d| d.abs() < 1.0e-6));
+    }
+}
@@ -1,5 +1,6 @@
 //! Statistics sub-module.
 
+pub mod least_squares;
 pub mod list;
 
-pub use self::list::*;
+pub use self::{least_squares::*, list::*};
@@ -0,0 +1,256 @@
+//! Bioheat implementation.
+
+use crate::{
+    access,
+    chem::{exposed, neighbours, BODY_TEMP},
+    dom::Regular,
+    phys::{Coagulation, Loss},
+    uni::Verse,
+};
+use nalgebra::Vector3;
+use ndarray::{Array3, Zip};
+use physical_constants::STEFAN_BOLTZMANN_CONSTANT;
+
+/// Density of blood. [kg m^-3]
+pub const BLOOD_DENS: f64 = 1060.0;
+/// Specific heat capacity of blood. [J kg^-1 K^-1]
+pub const BLOOD_HEAT_CAP: f64 = 3617.0;
+
+/// Fraction of the explicit stability limit used for each conduction sub-step.
+const CFL_SAFETY: f64 = 0.9;
+
+/// Finite-volume Pennes bioheat operator over a regular grid.
+/// Conduction between cells uses the harmonic mean of their conductivities.
+/// Cells of materials without thermal properties are held at their temperature and exchange no heat.
+/// The Arrhenius thermal damage integral of coagulating cells is accumulated alongside the temperatures.
+pub struct Bioheat<'a> {
+    /// Thermal conductivity of each cell. [W m^-1 K^-1]
+    conds: Array3<f64>,
+    /// Volumetric heat capacity of each cell. [J m^-3 K^-1]
+    caps: Array3<f64>,
+    /// Blood perfusion heat exchange coefficient of each cell. [W m^-3 K^-1]
+    perfs: Array3<f64>,
+    /// Absorbed light and metabolic heat source of each cell. [W m^-3]
+    sources: Array3<f64>,
+    /// Cell widths. [m]
+    cell_size: Vector3<f64>,
+    /// Surface cells, their exposed area per unit volume [m^-1], and the convective coefficient [W m^-2 K^-1],
+    /// emissivity and ambient temperature [K] of their loss.
+    losses: Vec<([usize; 3], f64, f64, f64, f64)>,
+    /// Arterial blood temperature. [K]
+    arterial: f64,
+    /// Thermal damage properties of each cell's material, if it coagulates.
+    coags: Array3<Option<&'a Coagulation>>,
+}
+
+impl<'a> Bioheat<'a> {
+    access!(conds, Array3<f64>);
+    access!(caps, Array3<f64>);
+    access!(perfs, Array3<f64>);
+    access!(sources, sources_mut, Array3<f64>);
+    access!(cell_size, Vector3<f64>);
+    access!(losses, Vec<([usize; 3], f64, f64, f64, f64)>);
+    access!(arterial, f64);
+    access!(coags, Array3<Option<&'a Coagulation>>);
+
+    /// Construct a new instance without any absorbed light.
+    #[inline]
+    #[must_use]
+    pub fn new(verse: &'a Verse, grid: &Regular, losses: &[Loss], arterial: Option<f64>) -> Self {
+        let res = grid.res();
+
+        let mut cell_size = grid.bound().widths();
+        for (w, n) in cell_size.iter_mut().zip(res.iter()) {
+            *w /= *n as f64;
+        }
+
+        let mut conds = Array3::zeros(res);
+        let mut caps = Array3::zeros(res);
+        let mut perfs = Array3::zeros(res);
+        let mut sources = Array3::zeros(res);
+        let mut coags = Array3::from_elem(res, None);
+        for ((xi, yi, zi), cell) in grid.cells().indexed_iter() {
+            let mat = verse
+                .mats()
+                .map()
+                .get(cell.mat())
+                .expect("Invalid material name.");
+
+            let index = [xi, yi, zi];
+            if let Some(thermal) = mat.thermal() {
+                *conds.get_mut(index).expect("Invalid cell index.") = *thermal.cond();
+                *caps.get_mut(index).expect("Invalid cell index.") = thermal.vol_heat_cap();
+                *perfs.get_mut(index).expect("Invalid cell index.") =
+                    thermal.perfusion().unwrap_or(0.0) * BLOOD_DENS * BLOOD_HEAT_CAP;
+                *sources.get_mut(index).expect("Invalid cell index.") =
+                    thermal.metab().unwrap_or(0.0);
+            }
+            *coags.get_mut(index).expect("Invalid cell index.") = mat.coagulation().as_ref();
+        }
+
+        let mut surface = Vec::new();
+        for loss in losses {
+            for (index, exposure) in exposed(loss.inter(), verse, grid, &cell_size) {
+                if *caps.get(index).expect("Invalid cell index.") > 0.0 {
+                    surface.push((
+                        index,
+                        exposure,
+                        *loss.h(),
+                        *loss.emissivity(),
+                        *loss.ambient(),
+                    ));
+                }
+            }
+        }
+
+        Self {
+            conds,
+            caps,
+            perfs,
+            sources,
+            cell_size,
+            losses: surface,
+            arterial: arterial.unwrap_or(BODY_TEMP),
+            coags,
+        }
+    }
+
+    /// Add an absorbed power density to the heat sources. [W m^-3]
+    #[inline]
+    pub fn absorb(&mut self, abs_dens: &Array3<f64>) {
+        assert!(abs_dens.shape() == self.sources.shape());
+
+        self.sources += abs_dens;
+    }
+
+    /// Determine the largest stable explicit time step for the given temperatures. [s]
+    /// Radiative loss is linearised about the hottest of the temperatures and ambient temperatures.
+    #[inline]
+    #[must_use]
+    pub fn stable_dt(&self, temps: &Array3<f64>) -> f64 {
+        let res = self.conds.dim();
+        let res = [res.0, res.1, res.2];
+
+        let mut diag = self.perfs.clone();
+        for ((xi, yi, zi), d) in diag.indexed_iter_mut() {
+            for (next, dx) in neighbours([xi, yi, zi], res, &self.cell_size) {
+                *d += self.conductance([xi, yi, zi], next, dx) / dx;
+            }
+        }
+        let hottest = temps.iter().cloned().fold(self.arterial, f64::max);
+        for (index, exposure, h, emissivity, ambient) in &self.losses {
+            let rad = 4.0 * emissivity * STEFAN_BOLTZMANN_CONSTANT * hottest.max(*ambient).powi(3);
+            *diag.get_mut(*index).expect("Invalid cell index.") += exposure * (h + rad);
+        }
+
+        let max_rate = diag
+            .iter()
+            .zip(self.caps.iter())
+            .filter(|(_d, c)| **c > 0.0)
+            .map(|(d, c)| d / c)
+            .fold(0.0, f64::max);
+
+        if max_rate > 0.0 {
+            1.0 / max_rate
+        } else {
+            std::f64::INFINITY
+        }
+    }
+
+    /// Determine the thermal conductance between a cell and its neighbour. [W m^-2 K^-1]
+    #[inline]
+    #[must_use]
+    pub fn conductance(&self, index: [usize; 3], next: [usize; 3], dx: f64) -> f64 {
+        let k = *self.conds.get(index).expect("Invalid cell index.");
+        let k_next = *self.conds.get(next).expect("Invalid cell index.");
+        let c = *self.caps.get(index).expect("Invalid cell index.");
+        let c_next = *self.caps.get(next).expect("Invalid cell index.");
+        if k <= 0.0 || k_next <= 0.0 || c <= 0.0 || c_next <= 0.0 {
+            return 0.0;
+        }
+
+        2.0 * k * k_next / ((k + k_next) * dx)
+    }
+
+    /// Calculate the rate of change of the temperatures. [K s^-1]
+    #[inline]
+    #[must_use]
+    pub fn rate(&self, temps: &Array3<f64>) -> Array3<f64> {
+        assert!(temps.shape() == self.caps.shape());
+
+        let res = self.caps.dim();
+        let res = [res.0, res.1, res.2];
+        let mut power = &self.sources + &(&self.perfs * &temps.mapv(|t| self.arterial - t));
+
+        for ((xi, yi, zi), p) in power.indexed_iter_mut() {
+            let t = temps.get([xi, yi, zi]).expect("Invalid cell index.");
+            for (next, dx) in neighbours([xi, yi, zi], res, &self.cell_size) {
+                let t_next = temps.get(next).expect("Invalid cell index.");
+                *p += self.conductance([xi, yi, zi], next, dx) * (t_next - t) / dx;
+            }
+        }
+        for (index, exposure, h, emissivity, ambient) in &self.losses {
+            let t = *temps.get(*index).expect("Invalid cell index.");
+            *power.get_mut(*index).expect("Invalid cell index.") -= exposure
+                * ((h * (t - ambient))
+                    + (emissivity * STEFAN_BOLTZMANN_CONSTANT * (t.powi(4) - ambient.powi(4))));
+        }
+
+        Zip::from(&mut power)
+            .and(&self.caps)
+            .apply(|p, c| *p = if *c > 0.0 { *p / c } else { 0.0 });
+
+        power
+    }
+
+    /// Calculate the rate of thermal damage of each cell at the given temperatures. [s^-1]
+    #[inline]
+    #[must_use]
+    pub fn damage_rate(&self, temps: &Array3<f64>) -> Array3<f64> {
+        assert!(temps.shape() == self.coags.shape());
+
+        let mut rate = Array3::zeros(temps.dim());
+        Zip::from(&mut rate)
+            .and(temps)
+            .and(&self.coags)
+            .apply(|r, t, coag| {
+                if let Some(coag) = coag {
+                    *r = coag.rate(*t);
+                }
+            });
+
+        rate
+    }
+
+    /// Evolve the temperatures over a given duration using stable explicit sub-steps.
+    /// The damage integrals are accumulated using the trapezium rule over each sub-step.
+    /// Returns the number of sub-steps taken.
+    #[inline]
+    pub fn evolve(
+        &self,
+        temps: &mut Array3<f64>,
+        damage: &mut Array3<f64>,
+        duration: f64,
+    ) -> usize {
+        assert!(duration >= 0.0);
+        assert!(damage.shape() == temps.shape());
+
+        if duration <= 0.0 {
+            return 0;
+        }
+
+        let max_dt = self.stable_dt(temps);
+        let steps = (duration / (CFL_SAFETY * max_dt)).ceil().max(1.0) as usize;
+        let dt = duration / steps as f64;
+        let mut damage_rate = self.damage_rate(temps);
+        for _ in 0..steps {
+            *temps += &(self.rate(temps) * dt);
+
+            let next_rate = self.damage_rate(temps);
+            *damage += &((&damage_rate + &next_rate) * (0.5 * dt));
+            damage_rate = next_rate;
+        }
+
+        steps
+    }
+}
@@ -0,0 +1,59 @@
+//! Coagulation implementation.
+
+use crate::{access, chem::arrhenius, phys::Optics};
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Arrhenius thermal damage of a material, and its optical properties once coagulated.
+#[json]
+pub struct Coagulation {
+    /// Pre-exponential factor. [s^-1]
+    pre_exp: f64,
+    /// Activation energy. [J mol^-1]
+    activation: f64,
+    /// Optional optical properties of cells whose damage integral has reached unity.
+    /// The refractive index of the material is retained at its interfaces.
+    optics: Option<Optics>,
+}
+
+impl Coagulation {
+    access!(pre_exp, f64);
+    access!(activation, f64);
+    access!(optics, Option<Optics>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(pre_exp: f64, activation: f64, optics: Option<Optics>) -> Self {
+        assert!(pre_exp >= 0.0);
+        assert!(activation >= 0.0);
+
+        Self {
+            pre_exp,
+            activation,
+            optics,
+        }
+    }
+
+    /// Calculate the rate of damage at a given temperature. [s^-1]
+    #[inline]
+    #[must_use]
+    pub fn rate(&self, temp: f64) -> f64 {
+        arrhenius(self.pre_exp, self.activation, temp)
+    }
+}
+
+impl Display for Coagulation {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "Coagulation (A: {:e} s^-1, Ea: {} J mol^-1)",
+            self.pre_exp, self.activation
+        )?;
+        if self.optics.is_some() {
+            write!(fmt, " with coagulated optics")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! Loss implementation.
+
+use crate::{access, dom::Name};
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Convective and radiative heat loss across an interface to its outside material.
+#[json]
+pub struct Loss {
+    /// Interface, by name or surface name, losing heat from its inside material.
+    inter: Name,
+    /// Convective heat transfer coefficient. [W m^-2 K^-1]
+    h: f64,
+    /// Surface emissivity.
+    emissivity: f64,
+    /// Ambient temperature. [K]
+    ambient: f64,
+}
+
+impl Loss {
+    access!(inter, Name);
+    access!(h, f64);
+    access!(emissivity, f64);
+    access!(ambient, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(inter: Name, h: f64, emissivity: f64, ambient: f64) -> Self {
+        assert!(h >= 0.0);
+        assert!((0.0..=1.0).contains(&emissivity));
+        assert!(ambient > 0.0);
+
+        Self {
+            inter,
+            h,
+            emissivity,
+            ambient,
+        }
+    }
+}
+
+impl Display for Loss {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "{}\th: {} W m^-2 K^-1\temissivity: {}\tambient: {} K",
+            self.inter, self.h, self.emissivity, self.ambient
+        )
+    }
+}
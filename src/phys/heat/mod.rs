@@ -0,0 +1,8 @@
+//! Heat transfer sub-module.
+
+pub mod bioheat;
+pub mod coagulation;
+pub mod loss;
+pub mod thermal;
+
+pub use self::{bioheat::*, coagulation::*, loss::*, thermal::*};
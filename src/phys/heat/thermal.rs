@@ -0,0 +1,77 @@
+//! Thermal implementation.
+
+use crate::access;
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Thermal properties of a material.
+#[json]
+pub struct Thermal {
+    /// Thermal conductivity. [W m^-1 K^-1]
+    cond: f64,
+    /// Specific heat capacity. [J kg^-1 K^-1]
+    heat_cap: f64,
+    /// Density. [kg m^-3]
+    dens: f64,
+    /// Optional volumetric blood perfusion rate. [s^-1]
+    perfusion: Option<f64>,
+    /// Optional metabolic heat generation. [W m^-3]
+    metab: Option<f64>,
+}
+
+impl Thermal {
+    access!(cond, f64);
+    access!(heat_cap, f64);
+    access!(dens, f64);
+    access!(perfusion, Option<f64>);
+    access!(metab, Option<f64>);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        cond: f64,
+        heat_cap: f64,
+        dens: f64,
+        perfusion: Option<f64>,
+        metab: Option<f64>,
+    ) -> Self {
+        assert!(cond >= 0.0);
+        assert!(heat_cap > 0.0);
+        assert!(dens > 0.0);
+        assert!(perfusion.map_or(true, |w| w >= 0.0));
+
+        Self {
+            cond,
+            heat_cap,
+            dens,
+            perfusion,
+            metab,
+        }
+    }
+
+    /// Calculate the volumetric heat capacity. [J m^-3 K^-1]
+    #[inline]
+    #[must_use]
+    pub fn vol_heat_cap(&self) -> f64 {
+        self.dens * self.heat_cap
+    }
+}
+
+impl Display for Thermal {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "k: {} W m^-1 K^-1\tc: {} J kg^-1 K^-1\trho: {} kg m^-3",
+            self.cond, self.heat_cap, self.dens
+        )?;
+        if let Some(w) = self.perfusion {
+            write!(fmt, "\tperfusion: {} s^-1", w)?;
+        }
+        if let Some(q) = self.metab {
+            write!(fmt, "\tmetabolic: {} W m^-3", q)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Mie scattering sub-module.
+
+pub mod particles;
+pub mod radius;
+pub mod sphere;
+
+pub use self::{particles::*, radius::*, sphere::*};
@@ -0,0 +1,208 @@
+//! Particles implementation.
+
+use crate::{
+    access,
+    math::Formula,
+    phys::{Environment, Optics, Phase, Radius, Sphere, Tabulated},
+};
+use attr::json;
+use nalgebra::Complex;
+use std::{
+    f64::consts::PI,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Number of quadrature points used to sample particle size distributions.
+const RAD_SAMPLES: usize = 64;
+
+/// Number of scattering angles used to tabulate phase functions.
+const PHASE_SAMPLES: usize = 181;
+
+/// Suspension of spherical scattering particles within a host medium.
+#[json]
+pub struct Particles {
+    /// Particle refractive index.
+    part_ref_index: Formula,
+    /// Optional particle extinction index (imaginary refractive index component).
+    part_ext_index: Option<Formula>,
+    /// Medium refractive index.
+    med_ref_index: Formula,
+    /// Optional medium absorption coefficient. [m^-1]
+    med_abs_coeff: Option<Formula>,
+    /// Particle radius distribution.
+    rad: Radius,
+    /// Volume fraction occupied by the particles.
+    vol_frac: f64,
+}
+
+impl Particles {
+    access!(part_ref_index, Formula);
+    access!(part_ext_index, Option<Formula>);
+    access!(med_ref_index, Formula);
+    access!(med_abs_coeff, Option<Formula>);
+    access!(rad, Radius);
+    access!(vol_frac, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        part_ref_index: Formula,
+        part_ext_index: Option<Formula>,
+        med_ref_index: Formula,
+        med_abs_coeff: Option<Formula>,
+        rad: Radius,
+        vol_frac: f64,
+    ) -> Self {
+        assert!(vol_frac > 0.0);
+        assert!(vol_frac < 1.0);
+
+        Self {
+            part_ref_index,
+            part_ext_index,
+            med_ref_index,
+            med_abs_coeff,
+            rad,
+            vol_frac,
+        }
+    }
+
+    /// Calculate the particle number density. [m^-3]
+    #[inline]
+    #[must_use]
+    pub fn num_dens(&self) -> f64 {
+        self.vol_frac / self.rad.mean_vol(RAD_SAMPLES)
+    }
+
+    /// Solve the sampled particle sizes for a given wavelength.
+    /// Returns the radius, number fraction weight and solution of each sample.
+    #[inline]
+    #[must_use]
+    pub fn solve(&self, w: f64) -> Vec<(f64, f64, Sphere)> {
+        assert!(w > 0.0);
+
+        let n_med = self.med_ref_index.y(w);
+        let rel_index = Complex::new(
+            self.part_ref_index.y(w),
+            self.part_ext_index.as_ref().map_or(0.0, |k| k.y(w)),
+        ) / n_med;
+        let wavenumber = 2.0 * PI * n_med / w;
+
+        self.rad
+            .samples(RAD_SAMPLES)
+            .into_iter()
+            .map(|(r, weight)| (r, weight, Sphere::new(wavenumber * r, rel_index)))
+            .collect()
+    }
+
+    /// Determine the bulk optical properties for a given wavelength.
+    #[inline]
+    #[must_use]
+    pub fn env(&self, w: f64) -> Environment {
+        let num_dens = self.num_dens();
+
+        let mut scat_coeff = 0.0;
+        let mut abs_coeff = self.med_abs_coeff.as_ref().map_or(0.0, |a| a.y(w));
+        let mut asym_sum = 0.0;
+        for (r, weight, sphere) in self.solve(w) {
+            let area = PI * r.powi(2);
+            let scat = num_dens * weight * area * sphere.q_sca();
+
+            scat_coeff += scat;
+            abs_coeff += num_dens * weight * area * sphere.q_abs();
+            asym_sum += scat * sphere.asym();
+        }
+
+        let asym = if scat_coeff > 0.0 {
+            asym_sum / scat_coeff
+        } else {
+            0.0
+        };
+
+        Environment::new(self.med_ref_index.y(w), scat_coeff, abs_coeff, 0.0, asym)
+    }
+
+    /// Calculate the scattering cross-section weighted phase function at the given scattering angle cosines.
+    /// Normalised such that its integral over the scattering angle cosine is unity.
+    #[inline]
+    #[must_use]
+    pub fn phase(&self, w: f64, mus: &[f64]) -> Vec<f64> {
+        let spheres = self.solve(w);
+        let total: f64 = spheres
+            .iter()
+            .map(|(r, weight, sphere)| weight * r.powi(2) * sphere.q_sca())
+            .sum();
+
+        mus.iter()
+            .map(|mu| {
+                spheres
+                    .iter()
+                    .map(|(r, weight, sphere)| {
+                        weight * r.powi(2) * sphere.q_sca() * sphere.phase(*mu)
+                    })
+                    .sum::<f64>()
+                    / total
+            })
+            .collect()
+    }
+
+    /// Tabulate the phase function for a given wavelength.
+    #[inline]
+    #[must_use]
+    pub fn phase_fn(&self, w: f64) -> Phase {
+        let mus: Vec<f64> = (0..PHASE_SAMPLES)
+            .map(|i| -(i as f64 / (PHASE_SAMPLES - 1) as f64 * PI).cos())
+            .collect();
+        let pdf = self.phase(w, &mus);
+
+        Phase::Tabulated(Tabulated::new(mus, pdf))
+    }
+
+    /// Generate the optical properties over a set of wavelengths.
+    /// A single wavelength produces constant properties and a tabulated phase function,
+    /// otherwise properties are tabulated over wavelength and scattering follows Henyey-Greenstein.
+    #[inline]
+    #[must_use]
+    pub fn optics(&self, ws: &[f64]) -> Optics {
+        assert!(!ws.is_empty());
+
+        let envs: Vec<Environment> = ws.iter().map(|w| self.env(*w)).collect();
+        let formula = |f: &dyn Fn(&Environment) -> f64| -> Formula {
+            if envs.len() == 1 {
+                Formula::Constant {
+                    c: f(envs.first().expect("Missing environment.")),
+                }
+            } else {
+                Formula::Tabulated {
+                    xs: ws.to_vec(),
+                    ys: envs.iter().map(f).collect(),
+                }
+            }
+        };
+
+        Optics::new(
+            formula(&|env| *env.ref_index()),
+            formula(&|env| *env.scat_coeff()),
+            formula(&|env| *env.abs_coeff()),
+            formula(&|env| *env.shift_coeff()),
+            formula(&|env| *env.asym()),
+            if ws.len() == 1 {
+                Some(self.phase_fn(*ws.first().expect("Missing wavelength.")))
+            } else {
+                None
+            },
+        )
+    }
+}
+
+impl Display for Particles {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(
+            fmt,
+            "{}\tvolume fraction: {}\tnumber density: {} m^-3",
+            self.rad,
+            self.vol_frac,
+            self.num_dens()
+        )
+    }
+}
@@ -0,0 +1,100 @@
+//! Radius distribution implementation.
+
+use attr::json;
+use std::{
+    f64::consts::PI,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Number of standard deviations either side of the mean to sample distributions over.
+const SPREAD: f64 = 4.0;
+
+/// Particle radius distributions. [m]
+#[json]
+pub enum Radius {
+    /// Monodisperse. r
+    Mono(f64),
+    /// Normal distribution. (mean, standard deviation)
+    Normal(f64, f64),
+    /// Log-normal distribution. (median, geometric standard deviation)
+    LogNormal(f64, f64),
+}
+
+impl Radius {
+    /// Sample the distribution with a given number of quadrature points.
+    /// Returns a list of radii and their normalised number fraction weights.
+    #[inline]
+    #[must_use]
+    pub fn samples(&self, num: usize) -> Vec<(f64, f64)> {
+        assert!(num > 0);
+
+        let mut samples = match self {
+            Self::Mono(r) => {
+                assert!(*r > 0.0);
+                return vec![(*r, 1.0)];
+            }
+            Self::Normal(mean, std) => {
+                assert!(*mean > 0.0);
+                assert!(*std > 0.0);
+
+                let min = (mean - (SPREAD * std)).max(mean * 1.0e-3);
+                let max = mean + (SPREAD * std);
+                let delta = (max - min) / num as f64;
+
+                (0..num)
+                    .map(|i| {
+                        let r = delta.mul_add(i as f64 + 0.5, min);
+                        (r, (-0.5 * ((r - mean) / std).powi(2)).exp())
+                    })
+                    .collect::<Vec<_>>()
+            }
+            Self::LogNormal(median, geo_std) => {
+                assert!(*median > 0.0);
+                assert!(*geo_std > 1.0);
+
+                let mu = median.ln();
+                let sigma = geo_std.ln();
+                let min = mu - (SPREAD * sigma);
+                let delta = (2.0 * SPREAD * sigma) / num as f64;
+
+                (0..num)
+                    .map(|i| {
+                        let ln_r = delta.mul_add(i as f64 + 0.5, min);
+                        (ln_r.exp(), (-0.5 * ((ln_r - mu) / sigma).powi(2)).exp())
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let total: f64 = samples.iter().map(|(_r, w)| w).sum();
+        for (_r, w) in &mut samples {
+            *w /= total;
+        }
+
+        samples
+    }
+
+    /// Calculate the mean particle volume. [m^3]
+    #[inline]
+    #[must_use]
+    pub fn mean_vol(&self, num: usize) -> f64 {
+        self.samples(num)
+            .iter()
+            .map(|(r, w)| w * 4.0 / 3.0 * PI * r.powi(3))
+            .sum()
+    }
+}
+
+impl Display for Radius {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Mono(r) => write!(fmt, "Mono: {}nm", r * 1.0e9),
+            Self::Normal(mean, std) => {
+                write!(fmt, "Normal: {}nm +/- {}nm", mean * 1.0e9, std * 1.0e9)
+            }
+            Self::LogNormal(median, geo_std) => {
+                write!(fmt, "LogNormal: {}nm x/ {}", median * 1.0e9, geo_std)
+            }
+        }
+    }
+}
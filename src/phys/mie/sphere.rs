@@ -0,0 +1,186 @@
+//! Single sphere Mie solution.
+
+use crate::access;
+use nalgebra::Complex;
+
+/// Mie scattering solution for a single homogeneous sphere.
+pub struct Sphere {
+    /// Size parameter. x = 2 pi r n_med / wavelength
+    size_param: f64,
+    /// Extinction efficiency.
+    q_ext: f64,
+    /// Scattering efficiency.
+    q_sca: f64,
+    /// Asymmetry parameter.
+    asym: f64,
+    /// Electric multipole coefficients.
+    a_ns: Vec<Complex<f64>>,
+    /// Magnetic multipole coefficients.
+    b_ns: Vec<Complex<f64>>,
+}
+
+impl Sphere {
+    access!(size_param, f64);
+    access!(q_ext, f64);
+    access!(q_sca, f64);
+    access!(asym, f64);
+
+    /// Solve for a sphere of a given size parameter and index relative to the surrounding medium.
+    /// Uses the downward logarithmic derivative recurrence of Bohren and Huffman.
+    #[inline]
+    #[must_use]
+    pub fn new(size_param: f64, rel_index: Complex<f64>) -> Self {
+        assert!(size_param > 0.0);
+        assert!(rel_index.re > 0.0);
+        assert!(rel_index.im >= 0.0);
+
+        let x = size_param;
+        let mx = rel_index * x;
+
+        let n_stop = (x + (4.0 * x.cbrt()) + 2.0).round() as usize;
+        let n_max = n_stop.max(mx.norm().round() as usize) + 15;
+
+        let mut log_derivs = vec![Complex::new(0.0, 0.0); n_max + 1];
+        for n in (1..=n_max).rev() {
+            let nmx = Complex::new(n as f64, 0.0) / mx;
+            *log_derivs.get_mut(n - 1).expect("Invalid index.") =
+                nmx - (Complex::new(1.0, 0.0) / (log_derivs.get(n).expect("Invalid index.") + nmx));
+        }
+
+        let mut psi_0 = x.cos();
+        let mut psi_1 = x.sin();
+        let mut chi_0 = -x.sin();
+        let mut chi_1 = x.cos();
+        let mut xi_1 = Complex::new(psi_1, -chi_1);
+
+        let mut a_ns = Vec::with_capacity(n_stop);
+        let mut b_ns = Vec::with_capacity(n_stop);
+        let mut q_ext = 0.0;
+        let mut q_sca = 0.0;
+        for n in 1..=n_stop {
+            let nf = n as f64;
+            let psi = (((2.0 * nf) - 1.0) * psi_1 / x) - psi_0;
+            let chi = (((2.0 * nf) - 1.0) * chi_1 / x) - chi_0;
+            let xi = Complex::new(psi, -chi);
+
+            let d_n = *log_derivs.get(n).expect("Invalid index.");
+            let ta = (d_n / rel_index) + (nf / x);
+            let tb = (d_n * rel_index) + (nf / x);
+            let a_n = ((ta * psi) - psi_1) / ((ta * xi) - xi_1);
+            let b_n = ((tb * psi) - psi_1) / ((tb * xi) - xi_1);
+
+            q_sca += ((2.0 * nf) + 1.0) * (a_n.norm_sqr() + b_n.norm_sqr());
+            q_ext += ((2.0 * nf) + 1.0) * (a_n + b_n).re;
+
+            a_ns.push(a_n);
+            b_ns.push(b_n);
+
+            psi_0 = psi_1;
+            psi_1 = psi;
+            chi_0 = chi_1;
+            chi_1 = chi;
+            xi_1 = Complex::new(psi_1, -chi_1);
+        }
+
+        q_sca *= 2.0 / x.powi(2);
+        q_ext *= 2.0 / x.powi(2);
+
+        let mut g_sum = 0.0;
+        for (i, (a_n, b_n)) in a_ns.iter().zip(&b_ns).enumerate() {
+            let nf = (i + 1) as f64;
+            g_sum += ((2.0 * nf) + 1.0) / (nf * (nf + 1.0)) * (a_n * b_n.conj()).re;
+
+            if let (Some(a_m), Some(b_m)) = (a_ns.get(i + 1), b_ns.get(i + 1)) {
+                g_sum +=
+                    (nf * (nf + 2.0)) / (nf + 1.0) * ((a_n * a_m.conj()) + (b_n * b_m.conj())).re;
+            }
+        }
+        let asym = 4.0 * g_sum / (x.powi(2) * q_sca);
+
+        Self {
+            size_param,
+            q_ext,
+            q_sca,
+            asym,
+            a_ns,
+            b_ns,
+        }
+    }
+
+    /// Calculate the absorption efficiency.
+    #[inline]
+    #[must_use]
+    pub fn q_abs(&self) -> f64 {
+        (self.q_ext - self.q_sca).max(0.0)
+    }
+
+    /// Calculate the scattering amplitudes (S1, S2) for a given scattering angle cosine.
+    #[inline]
+    #[must_use]
+    pub fn amplitudes(&self, mu: f64) -> (Complex<f64>, Complex<f64>) {
+        assert!(mu.abs() <= 1.0);
+
+        let mut s_1 = Complex::new(0.0, 0.0);
+        let mut s_2 = Complex::new(0.0, 0.0);
+
+        let mut pi_0 = 0.0;
+        let mut pi_1 = 1.0;
+        for (i, (a_n, b_n)) in self.a_ns.iter().zip(&self.b_ns).enumerate() {
+            let nf = (i + 1) as f64;
+            let tau = (nf * mu * pi_1) - ((nf + 1.0) * pi_0);
+            let f_n = ((2.0 * nf) + 1.0) / (nf * (nf + 1.0));
+
+            s_1 += ((a_n * pi_1) + (b_n * tau)) * f_n;
+            s_2 += ((a_n * tau) + (b_n * pi_1)) * f_n;
+
+            let pi = ((((2.0 * nf) + 1.0) * mu * pi_1) - ((nf + 1.0) * pi_0)) / nf;
+            pi_0 = pi_1;
+            pi_1 = pi;
+        }
+
+        (s_1, s_2)
+    }
+
+    /// Calculate the phase function value for a given scattering angle cosine.
+    /// Normalised such that its integral over the scattering angle cosine is unity.
+    #[inline]
+    #[must_use]
+    pub fn phase(&self, mu: f64) -> f64 {
+        let (s_1, s_2) = self.amplitudes(mu);
+
+        (s_1.norm_sqr() + s_2.norm_sqr()) / (self.size_param.powi(2) * self.q_sca)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bohren_huffman() {
+        // Worked example of Bohren and Huffman, appendix A: r = 0.525 um, wavelength = 0.6328 um, m = 1.55.
+        let x = 2.0 * std::f64::consts::PI * 0.525 / 0.6328;
+        let sphere = Sphere::new(x, Complex::new(1.55, 0.0));
+
+        assert!((sphere.q_ext() - 3.1054).abs() < 1.0e-3);
+        assert!((sphere.q_sca() - 3.1054).abs() < 1.0e-3);
+        assert!(sphere.q_abs() < 1.0e-9);
+
+        let (s_1, s_2) = sphere.amplitudes(-1.0);
+        let q_back = 4.0 * s_1.norm_sqr().max(s_2.norm_sqr()) / x.powi(2);
+        assert!((q_back - 2.9253).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn rayleigh_limit() {
+        let (x, m) = (0.01, Complex::new(1.33, 0.0));
+        let sphere = Sphere::new(x, m);
+
+        let m_sq = m * m;
+        let pol = ((m_sq - 1.0) / (m_sq + 2.0)).norm_sqr();
+        let q_sca = 8.0 / 3.0 * x.powi(4) * pol;
+
+        assert!(((sphere.q_sca() / q_sca) - 1.0).abs() < 1.0e-3);
+        assert!(sphere.asym().abs() < 1.0e-3);
+    }
+}
@@ -1,6 +1,8 @@
 //! Physics module.
 
+pub mod heat;
+pub mod mie;
 pub mod opt;
 pub mod part;
 
-pub use self::{opt::*, part::*};
+pub use self::{heat::*, mie::*, opt::*, part::*};
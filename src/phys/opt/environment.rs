@@ -50,6 +50,32 @@ impl Environment {
         self.scat_coeff + self.abs_coeff + self.shift_coeff
     }
 
+    /// Construct a copy with the scattering coefficient scaled by a given factor.
+    #[inline]
+    #[must_use]
+    pub fn scale_scat(&self, mult: f64) -> Self {
+        Self::new(
+            self.ref_index,
+            self.scat_coeff * mult,
+            self.abs_coeff,
+            self.shift_coeff,
+            self.asym,
+        )
+    }
+
+    /// Construct a copy with an additional absorption coefficient.
+    #[inline]
+    #[must_use]
+    pub fn add_abs(&self, abs_coeff: f64) -> Self {
+        Self::new(
+            self.ref_index,
+            self.scat_coeff,
+            self.abs_coeff + abs_coeff,
+            self.shift_coeff,
+            self.asym,
+        )
+    }
+
     /// Calculate the albedo.
     #[inline]
     #[must_use]
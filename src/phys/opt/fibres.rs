@@ -0,0 +1,76 @@
+//! Fibres implementation.
+
+use crate::access;
+use attr::json;
+use nalgebra::{Unit, Vector3};
+use rand::{rngs::ThreadRng, Rng};
+use std::fmt::{Display, Formatter, Result};
+
+/// Fibre orientation fields.
+#[json]
+pub enum Orient {
+    /// Uniform fibre axis.
+    Constant(Vector3<f64>),
+    /// Path to a per-cell map of fibre axes, of shape [nx, ny, nz, 3].
+    Map(String),
+}
+
+/// Aligned fibre scattering structure.
+/// The material scattering coefficient applies to photons travelling perpendicular to the fibre axis.
+#[json]
+pub struct Fibres {
+    /// Fibre orientation field.
+    orient: Orient,
+    /// Ratio of the scattering coefficient along the fibre axis to that perpendicular to it.
+    ratio: f64,
+}
+
+impl Fibres {
+    access!(orient, Orient);
+    access!(ratio, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(orient: Orient, ratio: f64) -> Self {
+        assert!(ratio > 0.0);
+
+        Self { orient, ratio }
+    }
+
+    /// Determine the scattering coefficient multiplier for a direction of travel relative to a fibre axis.
+    #[inline]
+    #[must_use]
+    pub fn scat_mult(&self, dir: &Unit<Vector3<f64>>, axis: &Unit<Vector3<f64>>) -> f64 {
+        assert!(self.ratio > 0.0);
+
+        let cos_sq = dir.dot(axis).powi(2);
+        self.ratio.mul_add(cos_sq, 1.0 - cos_sq)
+    }
+
+    /// Determine if a scattered direction should be accepted.
+    /// Directions are preferentially accepted in proportion to their scattering coefficient.
+    #[inline]
+    #[must_use]
+    pub fn accept(
+        &self,
+        rng: &mut ThreadRng,
+        dir: &Unit<Vector3<f64>>,
+        axis: &Unit<Vector3<f64>>,
+    ) -> bool {
+        rng.gen_range(0.0, 1.0) <= self.scat_mult(dir, axis) / self.ratio.max(1.0)
+    }
+}
+
+impl Display for Fibres {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match &self.orient {
+            Orient::Constant(axis) => write!(
+                fmt,
+                "Fibres ({}, {}, {}) ratio: {}",
+                axis.x, axis.y, axis.z, self.ratio
+            ),
+            Orient::Map(path) => write!(fmt, "Fibres [{}] ratio: {}", path, self.ratio),
+        }
+    }
+}
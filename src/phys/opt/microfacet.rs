@@ -0,0 +1,67 @@
+//! Microfacet implementation.
+
+use attr::json;
+use nalgebra::{Unit, Vector3};
+use rand::{rngs::ThreadRng, Rng};
+use std::{
+    f64::consts::PI,
+    fmt::{Display, Formatter, Result},
+};
+
+/// Microfacet normal distributions of rough surfaces.
+#[json]
+pub enum Microfacet {
+    /// Beckmann distribution. (roughness)
+    Beckmann(f64),
+    /// GGX (Trowbridge-Reitz) distribution. (roughness)
+    Ggx(f64),
+}
+
+impl Microfacet {
+    /// Sample a microfacet normal about a given macroscopic surface normal.
+    #[inline]
+    #[must_use]
+    pub fn sample_norm(
+        &self,
+        rng: &mut ThreadRng,
+        norm: &Unit<Vector3<f64>>,
+    ) -> Unit<Vector3<f64>> {
+        let u = rng.gen_range(0.0_f64, 1.0);
+        let tan_sq = match self {
+            Self::Beckmann(alpha) => {
+                assert!(*alpha > 0.0);
+                -alpha.powi(2) * (1.0 - u).ln()
+            }
+            Self::Ggx(alpha) => {
+                assert!(*alpha > 0.0);
+                alpha.powi(2) * u / (1.0 - u)
+            }
+        };
+        let cos_theta = 1.0 / (1.0 + tan_sq).sqrt();
+        let sin_theta = (1.0 - cos_theta.powi(2)).max(0.0).sqrt();
+        let phi = rng.gen_range(0.0, 2.0 * PI);
+
+        let arbitrary_axis = if norm.z.abs() < 0.9 {
+            Vector3::z_axis()
+        } else {
+            Vector3::x_axis()
+        };
+        let tangent = Unit::new_normalize(norm.cross(&arbitrary_axis));
+        let bitangent = norm.cross(&tangent);
+
+        Unit::new_normalize(
+            (tangent.as_ref() * (sin_theta * phi.cos()))
+                + (bitangent * (sin_theta * phi.sin()))
+                + (norm.as_ref() * cos_theta),
+        )
+    }
+}
+
+impl Display for Microfacet {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Beckmann(alpha) => write!(fmt, "Beckmann ({})", alpha),
+            Self::Ggx(alpha) => write!(fmt, "GGX ({})", alpha),
+        }
+    }
+}
@@ -2,7 +2,11 @@
 
 pub mod crossing;
 pub mod environment;
+pub mod fibres;
+pub mod microfacet;
 pub mod optics;
+pub mod phase;
 pub mod spectrum;
+pub mod tabulated;
 
-pub use self::{crossing::*, environment::*, optics::*, spectrum::*};
+pub use self::{crossing::*, environment::*, fibres::*, microfacet::*, optics::*, phase::*, spectrum::*, tabulated::*};
@@ -1,7 +1,12 @@
 //! Optics implementation.
 
-use crate::{access, math::Formula, phys::Environment};
+use crate::{
+    access,
+    math::{henyey_greenstein, Formula},
+    phys::{Environment, Phase},
+};
 use attr::json;
+use rand::rngs::ThreadRng;
 
 /// Optical properties structure.
 #[json]
@@ -16,6 +21,8 @@ pub struct Optics {
     shift_coeff: Formula,
     /// Asymmetry parameter.
     asym: Formula,
+    /// Optional phase function, Henyey-Greenstein with the asymmetry parameter otherwise.
+    phase: Option<Phase>,
 }
 
 impl Optics {
@@ -24,6 +31,7 @@ impl Optics {
     access!(abs_coeff, Formula);
     access!(shift_coeff, Formula);
     access!(asym, Formula);
+    access!(phase, Option<Phase>);
 
     /// Construct a new instance.
     #[inline]
@@ -34,6 +42,7 @@ impl Optics {
         abs_coeff: Formula,
         shift_coeff: Formula,
         asym: Formula,
+        phase: Option<Phase>,
     ) -> Self {
         Self {
             ref_index,
@@ -41,6 +50,7 @@ impl Optics {
             abs_coeff,
             shift_coeff,
             asym,
+            phase,
         }
     }
 
@@ -56,4 +66,15 @@ impl Optics {
             self.asym.y(w),
         )
     }
+
+    /// Sample a scattering angle within the given local environment.
+    #[inline]
+    #[must_use]
+    pub fn sample_scat(&self, rng: &mut ThreadRng, env: &Environment) -> f64 {
+        if let Some(phase) = &self.phase {
+            phase.sample(rng)
+        } else {
+            henyey_greenstein(rng, *env.asym())
+        }
+    }
 }
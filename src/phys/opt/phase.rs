@@ -0,0 +1,132 @@
+//! Phase function implementation.
+
+use crate::{
+    math::{henyey_greenstein, isotropic, rayleigh, reynolds_mccormick},
+    phys::Tabulated,
+};
+use attr::json;
+use rand::{rngs::ThreadRng, Rng};
+use std::fmt::{Display, Formatter, Result};
+
+/// Scattering phase functions.
+#[json]
+pub enum Phase {
+    /// Uniform scattering in all directions.
+    Isotropic,
+    /// Rayleigh scattering. p(mu) ~ 1 + mu^2
+    Rayleigh,
+    /// Henyey-Greenstein. (asymmetry)
+    HenyeyGreenstein(f64),
+    /// Two-term Henyey-Greenstein. (forward asymmetry, backward asymmetry, forward fraction)
+    TwoTermHenyeyGreenstein(f64, f64, f64),
+    /// Reynolds-McCormick (Gegenbauer kernel). (asymmetry, shape parameter)
+    ReynoldsMcCormick(f64, f64),
+    /// Modified Henyey-Greenstein. p(mu) = beta HG(g) + (1 - beta) 3/2 mu^2 (asymmetry, beta)
+    ModifiedHenyeyGreenstein(f64, f64),
+    /// Tabulated probability density over the ascending scattering angle cosines.
+    Tabulated(Tabulated),
+}
+
+impl Phase {
+    /// Sample a scattering angle.
+    #[inline]
+    #[must_use]
+    pub fn sample(&self, rng: &mut ThreadRng) -> f64 {
+        match self {
+            Self::Isotropic => isotropic(rng),
+            Self::Rayleigh => rayleigh(rng),
+            Self::HenyeyGreenstein(g) => henyey_greenstein(rng, *g),
+            Self::TwoTermHenyeyGreenstein(g_f, g_b, frac) => {
+                assert!(*frac >= 0.0 && *frac <= 1.0);
+
+                if rng.gen_range(0.0, 1.0) <= *frac {
+                    henyey_greenstein(rng, *g_f)
+                } else {
+                    henyey_greenstein(rng, *g_b)
+                }
+            }
+            Self::ReynoldsMcCormick(g, alpha) => reynolds_mccormick(rng, *g, *alpha),
+            Self::ModifiedHenyeyGreenstein(g, beta) => {
+                assert!(*beta >= 0.0 && *beta <= 1.0);
+
+                if rng.gen_range(0.0, 1.0) <= *beta {
+                    henyey_greenstein(rng, *g)
+                } else {
+                    rng.gen_range(-1.0_f64, 1.0).cbrt().acos()
+                }
+            }
+            Self::Tabulated(table) => table.sample(rng),
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Isotropic => write!(fmt, "Isotropic"),
+            Self::Rayleigh => write!(fmt, "Rayleigh"),
+            Self::HenyeyGreenstein(g) => write!(fmt, "HG (g: {})", g),
+            Self::TwoTermHenyeyGreenstein(g_f, g_b, frac) => {
+                write!(fmt, "TTHG (g_f: {}, g_b: {}, f: {})", g_f, g_b, frac)
+            }
+            Self::ReynoldsMcCormick(g, alpha) => write!(fmt, "RM (g: {}, alpha: {})", g, alpha),
+            Self::ModifiedHenyeyGreenstein(g, beta) => {
+                write!(fmt, "MHG (g: {}, beta: {})", g, beta)
+            }
+            Self::Tabulated(table) => write!(fmt, "Tabulated ({} points)", table.mus().len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of angles drawn per phase function.
+    const SAMPLES: i32 = 100_000;
+
+    /// Check the mean cosine of the sampled angles is within five standard errors of the expected value.
+    fn check_mean_cos(phase: &Phase, exact: f64) {
+        let mut rng = rand::thread_rng();
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for _ in 0..SAMPLES {
+            let mu = phase.sample(&mut rng).cos();
+            sum += mu;
+            sum_sq += mu * mu;
+        }
+        let mean = sum / f64::from(SAMPLES);
+        let std_err = ((sum_sq / f64::from(SAMPLES) - mean.powi(2)) / f64::from(SAMPLES)).sqrt();
+
+        assert!((mean - exact).abs() < 5.0 * std_err);
+    }
+
+    #[test]
+    fn analytic_mean_cos() {
+        check_mean_cos(&Phase::Isotropic, 0.0);
+        check_mean_cos(&Phase::Rayleigh, 0.0);
+        check_mean_cos(&Phase::HenyeyGreenstein(0.8), 0.8);
+        check_mean_cos(&Phase::ReynoldsMcCormick(0.7, 0.5), 0.7);
+    }
+
+    #[test]
+    fn two_term_mean_cos() {
+        let (g_f, g_b, frac) = (0.9, -0.4, 0.7);
+        check_mean_cos(
+            &Phase::TwoTermHenyeyGreenstein(g_f, g_b, frac),
+            (frac * g_f) + ((1.0 - frac) * g_b),
+        );
+    }
+
+    #[test]
+    fn modified_mean_cos() {
+        let (g, beta) = (0.85, 0.6);
+        check_mean_cos(&Phase::ModifiedHenyeyGreenstein(g, beta), beta * g);
+    }
+
+    #[test]
+    fn tabulated_mean_cos() {
+        // p(mu) ~ 1 + mu is exactly piecewise linear, so the mean cosine is 1/3.
+        let table = Tabulated::new(vec![-1.0, -0.2, 0.5, 1.0], vec![0.0, 0.8, 1.5, 2.0]);
+        check_mean_cos(&Phase::Tabulated(table), 1.0 / 3.0);
+    }
+}
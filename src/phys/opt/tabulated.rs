@@ -0,0 +1,98 @@
+//! Tabulated phase function implementation.
+
+use crate::access;
+use rand::{rngs::ThreadRng, Rng};
+use serde::{Deserialize, Serialize};
+
+/// Tabulated probability density over ascending scattering angle cosines.
+/// The cumulative distribution is formed once on construction, so sampling requires no allocation.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(from = "Table")]
+pub struct Tabulated {
+    /// Scattering angle cosines.
+    mus: Vec<f64>,
+    /// Probability densities.
+    pdf: Vec<f64>,
+    /// Cumulative (unnormalised) probability at each of the scattering angle cosines.
+    #[serde(skip)]
+    cdf: Vec<f64>,
+}
+
+/// Tabulated phase function construction structure.
+#[derive(Deserialize)]
+struct Table {
+    /// Scattering angle cosines.
+    mus: Vec<f64>,
+    /// Probability densities.
+    pdf: Vec<f64>,
+}
+
+impl From<Table> for Tabulated {
+    #[inline]
+    fn from(table: Table) -> Self {
+        Self::new(table.mus, table.pdf)
+    }
+}
+
+impl Tabulated {
+    access!(mus, Vec<f64>);
+    access!(pdf, Vec<f64>);
+
+    /// Construct a new instance.
+    /// Scattering angle cosines must be strictly ascending, probability densities need not be normalised.
+    #[inline]
+    #[must_use]
+    pub fn new(mus: Vec<f64>, pdf: Vec<f64>) -> Self {
+        assert!(mus.len() >= 2);
+        assert!(mus.len() == pdf.len());
+        assert!(mus
+            .iter()
+            .zip(mus.iter().skip(1))
+            .all(|(mu_0, mu_1)| mu_0 < mu_1));
+        assert!(pdf.iter().all(|p| *p >= 0.0));
+
+        let mut cdf = Vec::with_capacity(mus.len());
+        let mut sum = 0.0;
+        cdf.push(sum);
+        for ((mu_0, mu_1), (p_0, p_1)) in mus
+            .iter()
+            .zip(mus.iter().skip(1))
+            .zip(pdf.iter().zip(pdf.iter().skip(1)))
+        {
+            sum += (mu_1 - mu_0) * (p_0 + p_1) / 2.0;
+            cdf.push(sum);
+        }
+        assert!(sum > 0.0);
+
+        Self { mus, pdf, cdf }
+    }
+
+    /// Sample a scattering angle by inverting the cumulative distribution.
+    /// The density is linear across each bin, so the inversion within a bin is the root of a quadratic.
+    /// Bins of zero probability are never selected.
+    #[inline]
+    #[must_use]
+    pub fn sample(&self, rng: &mut ThreadRng) -> f64 {
+        let total = *self.cdf.last().expect("Missing value.");
+        let target = rng.gen_range(0.0, total);
+
+        let i = self
+            .cdf
+            .iter()
+            .skip(1)
+            .position(|c| *c > target)
+            .unwrap_or(self.cdf.len() - 2);
+        let c_0 = self.cdf.get(i).expect("Invalid index.");
+        let mu_0 = self.mus.get(i).expect("Invalid index.");
+        let mu_1 = self.mus.get(i + 1).expect("Invalid index.");
+        let p_0 = self.pdf.get(i).expect("Invalid index.");
+        let p_1 = self.pdf.get(i + 1).expect("Invalid index.");
+
+        let rem = target - c_0;
+        let slope = (p_1 - p_0) / (mu_1 - mu_0);
+        let den = p_0 + (2.0 * slope).mul_add(rem, p_0.powi(2)).max(0.0).sqrt();
+        let delta = if den > 0.0 { 2.0 * rem / den } else { 0.0 };
+
+        (mu_0 + delta).min(*mu_1).max(-1.0).min(1.0).acos()
+    }
+}
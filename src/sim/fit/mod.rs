@@ -0,0 +1,246 @@
+//! Kinetic parameter fitting sub-module.
+
+use crate::{
+    chem::{Local, Network, Reaction, BODY_TEMP},
+    data::Table,
+    dom::{index_of_name, Name, Set},
+    file::{Fitting, Load},
+    math::{integrate, levenberg_marquardt, Fit},
+    uni::Verse,
+};
+use log::{info, warn};
+use ndarray::Array1;
+use std::{collections::BTreeMap, path::Path};
+
+/// Error tolerance of the model integration.
+const INT_TOL: f64 = 1.0e-8;
+/// Relative finite difference step of the parameters, the square root of the integration tolerance.
+const REL_STEP: f64 = 1.0e-4;
+/// Normal quantile of the reported two-sided 95% confidence intervals.
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// Measured concentration time series.
+struct Series {
+    /// Measurement times. [s]
+    times: Vec<f64>,
+    /// Initial concentrations at the first measurement time.
+    init: Array1<f64>,
+    /// Measured species indices and their concentration columns.
+    cols: Vec<(usize, Vec<f64>)>,
+}
+
+impl Series {
+    /// Construct a new instance from a loaded table.
+    /// Measured species at the first time override the given initial concentrations.
+    fn new(table: &Table, verse: &Verse, init: &Array1<f64>) -> Self {
+        let times = table.column("time").expect("Missing time column.");
+        assert!(!times.is_empty());
+        assert!(times
+            .iter()
+            .zip(times.iter().skip(1))
+            .all(|(t0, t1)| t0 <= t1));
+
+        let mut init = init.clone();
+        let mut cols = Vec::new();
+        for heading in table.headings().iter().skip(1) {
+            let name = Name::new(heading);
+            if !verse.specs().map().contains_key(&name) {
+                warn!("Ignoring unknown species column: {}", heading);
+                continue;
+            }
+            let index = index_of_name(&name, verse.specs());
+            let col = table.column(heading).expect("Missing column.");
+            *init.get_mut(index).expect("Invalid species index.") =
+                *col.first().expect("Empty species column.");
+            cols.push((index, col));
+        }
+        assert!(!cols.is_empty());
+
+        Self { times, init, cols }
+    }
+
+    /// Predict the measured concentrations with the given network.
+    /// Returns the predicted value of each measured column at each time.
+    fn predict(&self, net: &Network, mult: f64, local: &Local) -> Vec<Vec<f64>> {
+        let mut concs = self.init.clone();
+        let mut dt = 0.0;
+        let mut time = *self.times.first().expect("Missing measurement times.");
+
+        let mut preds = vec![Vec::with_capacity(self.times.len()); self.cols.len()];
+        for t in &self.times {
+            integrate(
+                |cs| net.deriv(cs, mult, local),
+                |cs| net.jacobian(cs, mult, local),
+                &mut concs,
+                t - time,
+                &mut dt,
+                INT_TOL,
+            );
+            time = *t;
+
+            for ((index, _col), pred) in self.cols.iter().zip(preds.iter_mut()) {
+                pred.push(*concs.get(*index).expect("Invalid species index."));
+            }
+        }
+
+        preds
+    }
+}
+
+/// Rebuild the reaction set with the fitted parameters replaced by the given values.
+fn reacts(verse: &Verse, fitting: &Fitting, ps: &Array1<f64>) -> Set<Reaction> {
+    let mut map = BTreeMap::new();
+
+    for (name, react) in verse.reacts().map() {
+        let mut rate_ps = react.rate().params();
+        for ((fit_name, index, _lower, _upper), p) in fitting.params().iter().zip(ps.iter()) {
+            if fit_name == name {
+                *rate_ps
+                    .get_mut(*index)
+                    .expect("Invalid rate parameter index.") = *p;
+            }
+        }
+
+        map.insert(
+            name.clone(),
+            Reaction::new(
+                react.reactants().clone(),
+                react.products().clone(),
+                react.rate().with_params(&rate_ps),
+            ),
+        );
+    }
+
+    Set::new(map)
+}
+
+/// Fit the selected rate parameters of the reaction network to measured concentration time series.
+/// Returns a table of the initial guess, fitted value, standard error, and lower and upper 95% confidence limits of each parameter,
+/// and a table of the fitted values and residuals of each measured series.
+#[inline]
+#[must_use]
+pub fn run(in_dir: &Path, verse: &Verse, fitting: &Fitting) -> (Table, Vec<Table>) {
+    let mat = verse
+        .mats()
+        .map()
+        .get(fitting.mat())
+        .expect("Invalid material name.");
+    let mult = mat.reaction_multiplier().unwrap_or(0.0);
+    let local = Local::new(
+        fitting.fluence_rate().unwrap_or(0.0),
+        0.0,
+        fitting.temp().unwrap_or(BODY_TEMP),
+    );
+    let init = mat.init_state().as_ref().map_or_else(
+        || Array1::zeros(verse.specs().map().len()),
+        |state| state.build(verse.specs()).concs().clone(),
+    );
+
+    let series: Vec<Series> = fitting
+        .data()
+        .iter()
+        .map(|file| Series::new(&Table::load(&in_dir.join(file)), verse, &init))
+        .collect();
+
+    let mut ps: Array1<f64> = fitting
+        .params()
+        .iter()
+        .map(|(name, index, _lower, _upper)| {
+            let react = verse
+                .reacts()
+                .map()
+                .get(name)
+                .expect("Invalid reaction name.");
+            *react
+                .rate()
+                .params()
+                .get(*index)
+                .expect("Invalid rate parameter index.")
+        })
+        .collect();
+    let init_ps = ps.clone();
+    let lower = fitting.params().iter().map(|p| p.2).collect();
+    let upper = fitting.params().iter().map(|p| p.3).collect();
+
+    let residuals = |ps: &Array1<f64>| -> Array1<f64> {
+        let net = Network::new(&reacts(verse, fitting, ps), verse.specs());
+        let mut res = Vec::new();
+        for s in &series {
+            for ((_index, col), pred) in s.cols.iter().zip(s.predict(&net, mult, &local)) {
+                res.extend(pred.iter().zip(col).map(|(p, m)| p - m));
+            }
+        }
+        Array1::from(res)
+    };
+
+    let fit: Fit = levenberg_marquardt(
+        residuals,
+        &mut ps,
+        &lower,
+        &upper,
+        REL_STEP,
+        *fitting.tol(),
+        *fitting.max_iters(),
+    );
+    info!("Fit iterations: {}", fit.iters());
+    info!("Sum of squared residuals: {}", fit.sum_sq());
+
+    let std_errs = fit
+        .std_errs()
+        .unwrap_or_else(|| Array1::from_elem(ps.len(), std::f64::NAN));
+    let mut summary = Table::new(
+        fitting
+            .params()
+            .iter()
+            .map(|(name, index, _lower, _upper)| format!("{}_{}", name, index))
+            .collect(),
+    );
+    summary.push(init_ps.to_vec());
+    summary.push(fit.params().to_vec());
+    summary.push(std_errs.to_vec());
+    summary.push(
+        fit.params()
+            .iter()
+            .zip(std_errs.iter())
+            .map(|(p, e)| p - (CONFIDENCE_Z * e))
+            .collect(),
+    );
+    summary.push(
+        fit.params()
+            .iter()
+            .zip(std_errs.iter())
+            .map(|(p, e)| p + (CONFIDENCE_Z * e))
+            .collect(),
+    );
+
+    let net = Network::new(&reacts(verse, fitting, fit.params()), verse.specs());
+    let names: Vec<&Name> = verse.specs().map().keys().collect();
+    let tables = series
+        .iter()
+        .map(|s| {
+            let mut headings = vec!["time".to_string()];
+            for (index, _col) in &s.cols {
+                let name = names.get(*index).expect("Invalid species index.");
+                headings.push(format!("{}_fit", name));
+                headings.push(format!("{}_res", name));
+            }
+            let mut table = Table::new(headings);
+
+            let preds = s.predict(&net, mult, &local);
+            for (i, t) in s.times.iter().enumerate() {
+                let mut row = vec![*t];
+                for ((_index, col), pred) in s.cols.iter().zip(&preds) {
+                    let p = pred.get(i).expect("Missing prediction.");
+                    let m = col.get(i).expect("Missing measurement.");
+                    row.push(*p);
+                    row.push(p - m);
+                }
+                table.push(row);
+            }
+
+            table
+        })
+        .collect();
+
+    (summary, tables)
+}
@@ -0,0 +1,157 @@
+//! Bioheat simulation sub-module.
+
+use crate::{
+    chem::BODY_TEMP,
+    data::Table,
+    dom::Regular,
+    file::{Heating, Save},
+    phys::Bioheat,
+    sim::LightMap,
+    uni::Verse,
+};
+use log::{info, warn};
+use ndarray::{Array3, Zip};
+use std::path::Path;
+
+/// Solve the Pennes bioheat equation, heated by the light absorbed from the light map's source.
+/// The Arrhenius damage integral of each cell continues from its current value.
+/// Temperature and damage maps are saved at each of the output times,
+/// and the final temperatures and damage integrals are set as the local temperature and damage of each cell.
+/// Returns the mean and maximum temperature of the heat conducting cells, the fraction with a damage integral of at least unity,
+/// and the fraction above the pain threshold if given, at the start and each of the output times.
+#[inline]
+#[must_use]
+pub fn run(
+    verse: &Verse,
+    grid: &mut Regular,
+    light_map: &LightMap,
+    heating: &Heating,
+    out_dir: &Path,
+) -> Table {
+    let init_temp = heating.init_temp().unwrap_or(BODY_TEMP);
+    let mut bioheat = Bioheat::new(
+        verse,
+        grid,
+        heating
+            .losses()
+            .as_ref()
+            .map_or(&[][..], |losses| losses.as_slice()),
+        Some(init_temp),
+    );
+    let abs_dens = light_map.abs_dens();
+    bioheat.absorb(&abs_dens);
+
+    let mut temps = Array3::from_elem(grid.res(), init_temp);
+    let mut damage = grid.cells().map(|cell| *cell.state().damage());
+
+    let mut headings = vec![
+        "time".to_string(),
+        "mean_temp".to_string(),
+        "max_temp".to_string(),
+        "coag_frac".to_string(),
+    ];
+    if heating.pain().is_some() {
+        headings.push("pain_frac".to_string());
+    }
+    let mut table = Table::new(headings);
+    table.push(row(0.0, &bioheat, &temps, &damage, *heating.pain()));
+
+    let illum = heating.illum().unwrap_or(std::f64::INFINITY);
+    let mut targets = heating.times().clone();
+    if illum.is_finite() {
+        targets.push(illum);
+    }
+    targets.sort_by(|a, b| a.partial_cmp(b).expect("Invalid output time."));
+    targets.dedup();
+
+    let mut time = 0.0;
+    let mut peak = init_temp;
+    for t in targets {
+        assert!(t >= time);
+        let steps = bioheat.evolve(&mut temps, &mut damage, t - time);
+        time = t;
+
+        if (time - illum).abs() < std::f64::EPSILON {
+            info!("Light switched off at {}s", time);
+            *bioheat.sources_mut() -= &abs_dens;
+        }
+        if heating.times().contains(&time) {
+            info!("Heated to {}s in {} sub-steps", time, steps);
+            temps.save(&out_dir.join(format!("temp_{}s.nc", time)));
+            damage.save(&out_dir.join(format!("damage_{}s.nc", time)));
+            let r = row(time, &bioheat, &temps, &damage, *heating.pain());
+            peak = peak.max(*r.get(2).expect("Missing maximum temperature."));
+            table.push(r);
+        }
+    }
+
+    info!("Peak temperature: {}K", peak);
+    if let Some(pain) = heating.pain() {
+        if peak > *pain {
+            warn!("Peak temperature exceeds the pain threshold of {}K", pain);
+        }
+    }
+
+    Zip::from(grid.cells_mut())
+        .and(&temps)
+        .and(&damage)
+        .apply(|cell, temp, omega| {
+            let state = cell.state_mut();
+            *state.local_mut().temp_mut() = *temp;
+            *state.damage_mut() = *omega;
+        });
+
+    table
+}
+
+/// Form the mean and maximum temperature of the heat conducting cells, the fraction with a damage integral of at least unity,
+/// and the fraction above the pain threshold if given.
+fn row(
+    time: f64,
+    bioheat: &Bioheat,
+    temps: &Array3<f64>,
+    damage: &Array3<f64>,
+    pain: Option<f64>,
+) -> Vec<f64> {
+    let conducting: Vec<(f64, f64)> = temps
+        .iter()
+        .zip(damage.iter())
+        .zip(bioheat.caps().iter())
+        .filter(|(_td, c)| **c > 0.0)
+        .map(|((t, d), _c)| (*t, *d))
+        .collect();
+    let num = conducting.len().max(1) as f64;
+
+    let mut row = vec![
+        time,
+        conducting.iter().map(|(t, _d)| t).sum::<f64>() / num,
+        conducting
+            .iter()
+            .map(|(t, _d)| *t)
+            .fold(std::f64::NEG_INFINITY, f64::max),
+        conducting.iter().filter(|(_t, d)| *d >= 1.0).count() as f64 / num,
+    ];
+    if let Some(pain) = pain {
+        row.push(conducting.iter().filter(|(t, _d)| *t > pain).count() as f64 / num);
+    }
+
+    row
+}
+
+/// Count the cells which have coagulated, and so use their coagulated optical properties.
+#[inline]
+#[must_use]
+pub fn coagulated(verse: &Verse, grid: &Regular) -> usize {
+    grid.cells()
+        .iter()
+        .filter(|cell| {
+            verse
+                .mats()
+                .map()
+                .get(cell.mat())
+                .expect("Invalid material name.")
+                .coagulated(*cell.state().damage())
+                .is_some()
+        })
+        .count()
+}
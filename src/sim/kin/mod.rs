@@ -0,0 +1,357 @@
+//! Reaction kinetics simulation sub-module.
+
+use crate::{
+    access,
+    chem::{metabolism, Diffusion, Network},
+    data::Table,
+    dom::{index_of_name, Name, Regular},
+    file::{Kinetics, Save},
+    math::integrate,
+    sim::LightMap,
+    uni::Verse,
+    util::bar,
+};
+use log::info;
+use ndarray::{Array1, Array3, Axis, Zip};
+use std::{collections::BTreeMap, path::Path};
+
+/// Integrate the reactions, and optionally diffusion, within every cell of the grid.
+/// Stiff cells switch to an implicit reaction scheme.
+/// Concentration snapshots of each species are saved to the output directory at the snapshot times.
+/// Returns the mean species concentrations at the start and each of the output times.
+#[inline]
+#[must_use]
+pub fn run(verse: &Verse, grid: &mut Regular, kin: &Kinetics, out_dir: &Path) -> Table {
+    let net = Network::new(verse.reacts(), verse.specs());
+    let metabs = metabolisms(verse);
+    let mut diff = diffusion(verse, grid, kin);
+    if let Some(temp) = kin.temp() {
+        heat(grid, *temp);
+    }
+
+    let times = kin.times();
+    let snaps = kin
+        .snaps()
+        .as_ref()
+        .map_or(&[][..], |snaps| snaps.as_slice());
+    let tol = *kin.tol();
+
+    let mults = mults(verse, grid);
+    let chem = Chemistry::new(&net, &metabs, &mults, tol);
+    let mut dts: Array3<f64> = Array3::zeros(grid.res());
+
+    let mut table = Table::new(headings(verse, &diff));
+    table.push(row(0.0, grid, &diff));
+
+    let mut targets: Vec<f64> = times.iter().chain(snaps).cloned().collect();
+    targets.sort_by(|a, b| a.partial_cmp(b).expect("Invalid output time."));
+    targets.dedup();
+
+    if snaps.contains(&0.0) {
+        snapshot(0.0, verse, grid, out_dir, kin);
+    }
+
+    let pb = bar("Kinetics", targets.len() as u64);
+    let mut time = 0.0;
+    for t in targets {
+        pb.inc(1);
+
+        assert!(t >= time);
+        let duration = t - time;
+
+        evolve(&chem, &mut diff, grid, &mut dts, duration);
+
+        time = t;
+        if times.contains(&time) {
+            table.push(row(time, grid, &diff));
+        }
+        if snaps.contains(&time) {
+            snapshot(time, verse, grid, out_dir, kin);
+        }
+    }
+    pb.finish_with_message("Kinetics complete.");
+
+    table
+}
+
+/// Determine the reaction rate multiplier of each cell, inert cells have a multiplier of zero.
+#[inline]
+#[must_use]
+pub fn mults(verse: &Verse, grid: &Regular) -> Array3<f64> {
+    grid.cells().map(|cell| {
+        verse
+            .mats()
+            .map()
+            .get(cell.mat())
+            .expect("Invalid material name.")
+            .reaction_multiplier()
+            .unwrap_or(0.0)
+    })
+}
+
+/// Form the table headings of the time, each species, and the compartments of any plasma supply.
+#[inline]
+#[must_use]
+pub fn headings(verse: &Verse, diff: &Diffusion) -> Vec<String> {
+    let mut headings = vec!["time".to_string()];
+    for name in verse.specs().map().keys() {
+        headings.push(name.to_string());
+    }
+    if diff.plasma().is_some() {
+        headings.push("plasma".to_string());
+        headings.push("interstitial".to_string());
+    }
+
+    headings
+}
+
+/// Form the metabolic network of each consuming material.
+#[inline]
+#[must_use]
+pub fn metabolisms(verse: &Verse) -> BTreeMap<Name, Network> {
+    verse
+        .mats()
+        .map()
+        .iter()
+        .filter_map(|(name, mat)| {
+            mat.consumption()
+                .as_ref()
+                .map(|consumption| (name.clone(), metabolism(consumption, verse.specs())))
+        })
+        .collect()
+}
+
+/// Invariant reaction inputs of an evolving grid.
+pub struct Chemistry<'a> {
+    /// Reaction network.
+    net: &'a Network,
+    /// Metabolic network of each consuming material.
+    metabs: &'a BTreeMap<Name, Network>,
+    /// Reaction rate multiplier of each cell.
+    mults: &'a Array3<f64>,
+    /// Integration error tolerance.
+    tol: f64,
+}
+
+impl<'a> Chemistry<'a> {
+    access!(net, Network);
+    access!(metabs, BTreeMap<Name, Network>);
+    access!(mults, Array3<f64>);
+    access!(tol, f64);
+
+    /// Construct a new instance.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        net: &'a Network,
+        metabs: &'a BTreeMap<Name, Network>,
+        mults: &'a Array3<f64>,
+        tol: f64,
+    ) -> Self {
+        assert!(tol > 0.0);
+
+        Self {
+            net,
+            metabs,
+            mults,
+            tol,
+        }
+    }
+}
+
+/// Evolve the grid over a given duration.
+/// Reactions and diffusion are coupled by Strang splitting with steps limited by the diffusion stability limit.
+#[inline]
+pub fn evolve(
+    chem: &Chemistry,
+    diff: &mut Diffusion,
+    grid: &mut Regular,
+    dts: &mut Array3<f64>,
+    duration: f64,
+) {
+    let steps = (duration / diff.max_dt()).ceil().max(1.0) as usize;
+    let dt = duration / steps as f64;
+    for _ in 0..steps {
+        diffuse(diff, grid, dt * 0.5);
+        react(chem.net, chem.metabs, grid, chem.mults, dts, dt, chem.tol);
+        diffuse(diff, grid, dt * 0.5);
+    }
+}
+
+/// Integrate the reactions, metabolic consumption and species sources, within every cell of the grid over a given duration.
+#[inline]
+pub fn react(
+    net: &Network,
+    metabs: &BTreeMap<Name, Network>,
+    grid: &mut Regular,
+    mults: &Array3<f64>,
+    dts: &mut Array3<f64>,
+    duration: f64,
+    tol: f64,
+) {
+    Zip::from(grid.cells_mut())
+        .and(mults)
+        .and(dts)
+        .par_apply(|cell, mult, dt| {
+            let metab = metabs.get(cell.mat());
+            if *mult > 0.0 || metab.is_some() {
+                let local = cell.state().local().clone();
+                let sources = cell.state().sources().clone();
+                integrate(
+                    |concs| {
+                        let mut deriv = net.deriv(concs, *mult, &local) + &sources;
+                        if let Some(metab) = metab {
+                            deriv += &metab.deriv(concs, 1.0, &local);
+                        }
+                        deriv
+                    },
+                    |concs| {
+                        let mut jac = net.jacobian(concs, *mult, &local);
+                        if let Some(metab) = metab {
+                            jac += &metab.jacobian(concs, 1.0, &local);
+                        }
+                        jac
+                    },
+                    cell.state_mut().concs_mut(),
+                    duration,
+                    dt,
+                    tol,
+                );
+            } else if cell.state().sources().iter().any(|s| *s != 0.0) {
+                let sources = cell.state().sources() * duration;
+                *cell.state_mut().concs_mut() += &sources;
+            }
+        });
+}
+
+/// Set the local light conditions of each cell from the light map of the same cell.
+#[inline]
+pub fn illuminate(grid: &mut Regular, light_map: &LightMap) {
+    Zip::from(grid.cells_mut())
+        .and(&light_map.fluence_rate())
+        .and(&light_map.abs_dens())
+        .apply(|cell, fluence_rate, abs_dens| {
+            let local = cell.state_mut().local_mut();
+            *local.fluence_rate_mut() = *fluence_rate;
+            *local.abs_dens_mut() = *abs_dens;
+        });
+}
+
+/// Construct the diffusion operator, including boundary conditions, for the kinetics settings.
+#[inline]
+#[must_use]
+pub fn diffusion(verse: &Verse, grid: &Regular, kin: &Kinetics) -> Diffusion {
+    Diffusion::new(
+        verse,
+        grid,
+        *kin.temp(),
+        kin.faces()
+            .as_ref()
+            .map_or(&[][..], |faces| faces.as_slice()),
+        kin.topicals()
+            .as_ref()
+            .map_or(&[][..], |topicals| topicals.as_slice()),
+        kin.pk().as_ref(),
+    )
+}
+
+/// Set the local temperature of every cell.
+#[inline]
+pub fn heat(grid: &mut Regular, temp: f64) {
+    for cell in grid.cells_mut().iter_mut() {
+        *cell.state_mut().local_mut().temp_mut() = temp;
+    }
+}
+
+/// Diffuse the species throughout the grid over a given duration.
+#[inline]
+pub fn diffuse(diff: &mut Diffusion, grid: &mut Regular, duration: f64) {
+    let mut concs = grid.concs();
+    diff.evolve(&mut concs, duration);
+    grid.set_concs(&concs);
+}
+
+/// Save the concentration map of each species, and the hypoxia map if requested, at the given time.
+#[inline]
+pub fn snapshot(time: f64, verse: &Verse, grid: &Regular, out_dir: &Path, kin: &Kinetics) {
+    let concs = grid.concs();
+    for (index, name) in verse.specs().map().keys().enumerate() {
+        concs
+            .index_axis(Axis(3), index)
+            .to_owned()
+            .save(&out_dir.join(format!("{}_{}s.nc", name, time)));
+    }
+
+    if let Some((spec, threshold)) = kin.hypoxia() {
+        let frac = hypoxia(time, spec, *threshold, verse, grid, out_dir);
+        info!("Hypoxic fraction at {}s: {}", time, frac);
+    }
+}
+
+/// Save the map of hypoxic cells at the given time.
+/// Cells of reactive or metabolising materials are hypoxic when the species concentration is below the threshold.
+/// Returns the fraction of such cells which are hypoxic.
+#[inline]
+pub fn hypoxia(
+    time: f64,
+    spec: &Name,
+    threshold: f64,
+    verse: &Verse,
+    grid: &Regular,
+    out_dir: &Path,
+) -> f64 {
+    let index = index_of_name(spec, verse.specs());
+
+    let mut living = 0;
+    let mut hypoxic = 0;
+    let map = grid.cells().map(|cell| {
+        let mat = verse
+            .mats()
+            .map()
+            .get(cell.mat())
+            .expect("Invalid material name.");
+        if mat.reaction_multiplier().is_none() && mat.consumption().is_none() {
+            return 0.0;
+        }
+
+        living += 1;
+        if *cell.state().concs().get(index).expect("Invalid index.") < threshold {
+            hypoxic += 1;
+            1.0
+        } else {
+            0.0
+        }
+    });
+    map.save(&out_dir.join(format!("hypoxia_{}s.nc", time)));
+
+    if living > 0 {
+        f64::from(hypoxic) / f64::from(living)
+    } else {
+        0.0
+    }
+}
+
+/// Form a table row of the time, mean species concentrations over the grid, and any plasma compartment concentrations.
+#[inline]
+#[must_use]
+pub fn row(time: f64, grid: &Regular, diff: &Diffusion) -> Vec<f64> {
+    let mut total: Option<Array1<f64>> = None;
+    for cell in grid.cells() {
+        if let Some(total) = &mut total {
+            *total += cell.state().concs();
+        } else {
+            total = Some(cell.state().concs().clone());
+        }
+    }
+
+    let mut row = vec![time];
+    if let Some(total) = total {
+        row.extend((total / grid.cells().len() as f64).iter());
+    }
+    if let Some(plasma) = diff.plasma() {
+        row.push(plasma.plasma());
+        row.push(plasma.inter());
+    }
+
+    row
+}
@@ -1,8 +1,13 @@
 //! Light-Map structure.
 
-use crate::{access, file::Save, sim::Record};
+use crate::{
+    access,
+    dom::Name,
+    file::{as_json, Save},
+    sim::{Record, Tally},
+};
 use ndarray::Array3;
-use std::{ops::AddAssign, path::Path};
+use std::{collections::BTreeMap, ops::AddAssign, path::Path};
 
 /// Light-Map structure implementation.
 /// Stores output data from an MCRT simulation.
@@ -12,26 +17,71 @@ pub struct LightMap {
     recs: Array3<Record>,
     /// Cell volume [m^2].
     cell_vol: f64,
+    /// Non-refractive interface tallies.
+    tallies: BTreeMap<Name, Tally>,
+    /// Total power of the light source. [W]
+    power: f64,
 }
 
 impl LightMap {
     access!(recs, recs_mut, Array3<Record>);
     access!(cell_vol, f64);
+    access!(tallies, tallies_mut, BTreeMap<Name, Tally>);
+    access!(power, f64);
 
-    /// Construct a new instance.
+    /// Construct a new instance for a light source of the given total power.
     #[inline]
     #[must_use]
-    pub fn new(res: [usize; 3], cell_vol: f64) -> Self {
+    pub fn new(res: [usize; 3], cell_vol: f64, power: f64) -> Self {
+        assert!(power > 0.0);
+
         Self {
             recs: Array3::default(res),
             cell_vol,
+            tallies: BTreeMap::new(),
+            power,
+        }
+    }
+
+    /// Determine the power carried by each unit of emitted photon weight. [W]
+    /// Zero if no photons have been emitted, so the derived fields are empty rather than undefined.
+    #[inline]
+    #[must_use]
+    pub fn power_per_weight(&self) -> f64 {
+        let emissions: f64 = self.recs.iter().map(Record::emissions).sum();
+        if emissions <= 0.0 {
+            return 0.0;
         }
+
+        self.power / emissions
+    }
+
+    /// Estimate the fluence rate within each cell. [W m^-2]
+    #[inline]
+    #[must_use]
+    pub fn fluence_rate(&self) -> Array3<f64> {
+        let norm = self.power_per_weight() / self.cell_vol;
+        self.recs.map(|r| r.weighted_dist() * norm)
+    }
+
+    /// Estimate the absorbed power density within each cell. [W m^-3]
+    #[inline]
+    #[must_use]
+    pub fn abs_dens(&self) -> Array3<f64> {
+        let norm = self.power_per_weight() / self.cell_vol;
+        self.recs.map(|r| r.absorptions() * norm)
     }
 }
 
 impl AddAssign<&Self> for LightMap {
     fn add_assign(&mut self, rhs: &Self) {
+        assert!((self.power - rhs.power).abs() <= 1.0e-8 * self.power.max(rhs.power));
+
         self.recs += &rhs.recs;
+
+        for (name, tally) in &rhs.tallies {
+            *self.tallies.entry(name.clone()).or_default() += tally;
+        }
     }
 }
 
@@ -43,14 +93,14 @@ impl Save for LightMap {
         self.recs
             .map(|r| r.scatters() / self.cell_vol)
             .save(&out_dir.join("scat_dens.nc"));
-        self.recs
-            .map(|r| r.absorptions() / self.cell_vol)
-            .save(&out_dir.join("abs_dens.nc"));
+        self.abs_dens().save(&out_dir.join("abs_dens.nc"));
         self.recs
             .map(|r| r.shifts() / self.cell_vol)
             .save(&out_dir.join("shift_dens.nc"));
         self.recs
             .map(|r| r.dist_travelled() / self.cell_vol)
             .save(&out_dir.join("dist_travelled_dens.nc"));
+        self.fluence_rate().save(&out_dir.join("fluence_rate.nc"));
+        as_json(&self.tallies, &out_dir.join("tallies.json"));
     }
 }
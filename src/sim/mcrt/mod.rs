@@ -3,22 +3,25 @@
 pub mod hit;
 pub mod light_map;
 pub mod record;
+pub mod tally;
 
-pub use self::{hit::*, light_map::*, record::*};
+pub use self::{hit::*, light_map::*, record::*, tally::*};
 
 use crate::{
     access,
     dom::{Cell, Name, Regular, Set},
-    geom::Trace,
-    math::henyey_greenstein,
-    phys::{Crossing, Environment, Photon},
-    uni::{Material, Verse},
+    geom::{Ray, Trace},
+    phys::{Crossing, Environment, Microfacet, Optics, Photon},
+    uni::{Kind, Material, Verse},
     util::bar,
 };
 use log::warn;
-use nalgebra::Point3;
+use nalgebra::{Point3, Unit, Vector3};
 use rand::{rngs::ThreadRng, thread_rng, Rng};
-use std::f64::{consts::PI, MIN_POSITIVE};
+use std::{
+    collections::BTreeMap,
+    f64::{consts::PI, MIN_POSITIVE},
+};
 
 /// Maximum number of loops a photon will make before being culled prematurely.
 const MAX_LOOPS: u64 = 10_000;
@@ -26,6 +29,9 @@ const MAX_LOOPS: u64 = 10_000;
 /// Weight below which to perform roulette each photon loop.
 const ROULETTE: f64 = 0.1;
 
+/// Maximum number of microfacet normals sampled at a rough interface before treating it as smooth.
+const MAX_FACET_SAMPLES: u64 = 100;
+
 /// Generate a lightmap for a given setup.
 #[inline]
 #[must_use]
@@ -37,7 +43,8 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
     let mut rng = thread_rng();
 
     let light = &verse.lights().map().get(name).expect("Invalid light name.");
-    let mut light_map = LightMap::new(grid.res(), grid.cell_vol());
+    let mut light_map = LightMap::new(grid.res(), grid.cell_vol(), *light.power());
+    let mut surfs = Surfaces::new(verse.mats(), bump_dist);
     for _ in 0..num_phot {
         pb.inc(1);
 
@@ -46,13 +53,12 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
 
         let mut cell_rec = cell_and_record(phot.ray().pos(), grid, &mut light_map);
         *cell_rec.1.emissions_mut() += phot.weight();
-        let mut env = verse
+        let mut mat = verse
             .mats()
             .map()
             .get(cell_rec.0.mat())
-            .expect("Invalid material name.")
-            .optics()
-            .env(*phot.wavelength());
+            .expect("Invalid material name.");
+        let (mut optics, mut env) = resolve(mat, cell_rec.0, *phot.wavelength());
 
         let mut num_loops = 0;
         loop {
@@ -74,7 +80,11 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
                 }
             }
 
-            let scat_dist = -(rng.gen_range(0.0_f64, 1.0)).ln() / env.inter_coeff();
+            let local_env = env
+                .scale_scat(scat_mult(mat, cell_rec.0, phot.ray().dir()))
+                .add_abs(mat.chromo_abs(*phot.wavelength(), cell_rec.0.state().concs()));
+
+            let scat_dist = -(rng.gen_range(0.0_f64, 1.0)).ln() / local_env.inter_coeff();
             let cell_dist = cell_rec
                 .0
                 .bound()
@@ -85,18 +95,16 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
             match Hit::new(scat_dist, cell_dist, inter_dist, bump_dist) {
                 Hit::Scattering(dist) => {
                     *cell_rec.1.dist_travelled_mut() += dist;
+                    *cell_rec.1.weighted_dist_mut() += dist * phot.weight();
                     phot.ray_mut().travel(dist);
 
                     *cell_rec.1.scatters_mut() += phot.weight();
-                    phot.ray_mut().rotate(
-                        henyey_greenstein(&mut rng, *env.asym()),
-                        rng.gen_range(0.0, 2.0 * PI),
-                    );
+                    scatter(&mut rng, &mut phot, mat, cell_rec.0, optics, &env);
 
-                    *cell_rec.1.absorptions_mut() += env.albedo() * phot.weight();
-                    *phot.weight_mut() *= env.albedo();
+                    *cell_rec.1.absorptions_mut() += (1.0 - local_env.albedo()) * phot.weight();
+                    *phot.weight_mut() *= local_env.albedo();
 
-                    if !shifted && rng.gen_range(0.0, 1.0) <= env.shift_prob() {
+                    if !shifted && rng.gen_range(0.0, 1.0) <= local_env.shift_prob() {
                         *cell_rec.1.shifts_mut() += phot.weight();
                         shifted = true;
                     }
@@ -104,6 +112,7 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
                 Hit::Cell(dist) => {
                     let dist = dist + bump_dist;
                     *cell_rec.1.dist_travelled_mut() += dist;
+                    *cell_rec.1.weighted_dist_mut() += dist * phot.weight();
                     phot.ray_mut().travel(dist);
 
                     if !grid.bound().contains(phot.ray().pos()) {
@@ -111,17 +120,22 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
                     }
 
                     cell_rec = cell_and_record(phot.ray().pos(), grid, &mut light_map);
+                    let resolved = resolve(mat, cell_rec.0, *phot.wavelength());
+                    optics = resolved.0;
+                    env = resolved.1;
                 }
                 Hit::Interface(dist) => {
-                    hit_interface(
+                    if !hit_interface(
                         &mut rng,
                         &mut phot,
                         &mut cell_rec,
+                        &mut mat,
                         &mut env,
                         dist,
-                        bump_dist,
-                        verse.mats(),
-                    );
+                        &mut surfs,
+                    ) {
+                        break;
+                    }
 
                     if !cell_rec.0.bound().contains(phot.ray().pos()) {
                         // TODO: This should be able to be removed.
@@ -132,23 +146,32 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
                         warn!("It happened!");
                         cell_rec = cell_and_record(phot.ray().pos(), grid, &mut light_map);
                     }
+
+                    let resolved = resolve(mat, cell_rec.0, *phot.wavelength());
+                    optics = resolved.0;
+                    env = resolved.1;
                 }
                 Hit::InterfaceCell(dist) => {
-                    hit_interface(
+                    if !hit_interface(
                         &mut rng,
                         &mut phot,
                         &mut cell_rec,
+                        &mut mat,
                         &mut env,
                         dist,
-                        bump_dist,
-                        verse.mats(),
-                    );
+                        &mut surfs,
+                    ) {
+                        break;
+                    }
 
                     if !grid.bound().contains(phot.ray().pos()) {
                         break;
                     }
 
                     cell_rec = cell_and_record(phot.ray().pos(), grid, &mut light_map);
+                    let resolved = resolve(mat, cell_rec.0, *phot.wavelength());
+                    optics = resolved.0;
+                    env = resolved.1;
                 }
             }
         }
@@ -156,6 +179,8 @@ pub fn run(name: &Name, num_phot: u64, verse: &Verse, grid: &Regular) -> LightMa
 
     pb.finish_with_message("Photon loop complete.");
 
+    *light_map.tallies_mut() = surfs.tallies;
+
     light_map
 }
 
@@ -219,51 +244,233 @@ impl<'a> Info<'a> {
     }
 }
 
+/// Invariant interface hit inputs, and the tallies they accumulate.
+struct Surfaces<'a> {
+    /// Materials which may be entered on crossing an interface.
+    mats: &'a Set<Material>,
+    /// Distance to push photons past an interface.
+    bump_dist: f64,
+    /// Interface tallies.
+    tallies: BTreeMap<Name, Tally>,
+}
+
+impl<'a> Surfaces<'a> {
+    /// Construct a new instance with empty tallies.
+    #[inline]
+    #[must_use]
+    fn new(mats: &'a Set<Material>, bump_dist: f64) -> Self {
+        Self {
+            mats,
+            bump_dist,
+            tallies: BTreeMap::new(),
+        }
+    }
+}
+
+/// Determine the scattering coefficient multiplier for a direction of travel through a cell.
+#[inline]
+#[must_use]
+fn scat_mult(mat: &Material, cell: &Cell, dir: &Unit<Vector3<f64>>) -> f64 {
+    if let (Some(fibres), Some(axis)) = (mat.fibres(), cell.fibre()) {
+        return fibres.scat_mult(dir, axis);
+    }
+
+    1.0
+}
+
+/// Resolve the optical properties of a material within a cell, and their environment at a given wavelength.
+/// Coagulated cells use their coagulated optical properties.
+#[inline]
+#[must_use]
+fn resolve<'a>(mat: &'a Material, cell: &Cell, w: f64) -> (&'a Optics, Environment) {
+    let optics = mat
+        .coagulated(*cell.state().damage())
+        .unwrap_or_else(|| mat.optics());
+
+    (optics, optics.env(w))
+}
+
+/// Perform a scattering event, redirecting the photon with the phase function of the resolved optics.
+/// Within fibrous materials trial directions are rejected in proportion to their scattering coefficient.
+fn scatter(
+    rng: &mut ThreadRng,
+    phot: &mut Photon,
+    mat: &Material,
+    cell: &Cell,
+    optics: &Optics,
+    env: &Environment,
+) {
+    loop {
+        let mut trial = phot.ray().clone();
+        trial.rotate(optics.sample_scat(rng, env), rng.gen_range(0.0, 2.0 * PI));
+
+        if let (Some(fibres), Some(axis)) = (mat.fibres(), cell.fibre()) {
+            if !fibres.accept(rng, trial.dir(), axis) {
+                continue;
+            }
+        }
+
+        *phot.ray_mut() = trial;
+        return;
+    }
+}
+
 /// Perform an interface hit event.
-fn hit_interface(
+/// Returns false if the photon was terminated by the interface.
+fn hit_interface<'a>(
     rng: &mut ThreadRng,
     phot: &mut Photon,
     cell_rec: &mut (&Cell, &mut Record),
+    mat: &mut &'a Material,
     env: &mut Environment,
     dist: f64,
-    bump_dist: f64,
-    mats: &Set<Material>,
-) {
-    let (_dist, inside, norm, inter) = cell_rec
+    surfs: &mut Surfaces<'a>,
+) -> bool {
+    let bump_dist = surfs.bump_dist;
+    let tallies = &mut surfs.tallies;
+
+    let (_dist, inside, norm, (name, inter)) = cell_rec
         .0
         .inter_dist_inside_norm_inter(phot.ray())
         .expect("Failed to observe interface within cell.");
 
+    match inter.kind() {
+        Some(Kind::Absorber) | Some(Kind::Detector) => {
+            *cell_rec.1.dist_travelled_mut() += dist;
+            *cell_rec.1.weighted_dist_mut() += dist * phot.weight();
+            phot.ray_mut().travel(dist);
+
+            let tally = tallies.entry(name.clone()).or_default();
+            *tally.hits_mut() += 1.0;
+            *tally.weight_mut() += phot.weight();
+
+            return false;
+        }
+        Some(Kind::Mirror) => {
+            let effective_dist = (dist - bump_dist).max(MIN_POSITIVE);
+            *cell_rec.1.dist_travelled_mut() += effective_dist;
+            *cell_rec.1.weighted_dist_mut() += effective_dist * phot.weight();
+            phot.ray_mut().travel(effective_dist);
+            *phot.ray_mut().dir_mut() = *Crossing::new(phot.ray().dir(), &norm, 1.0, 1.0).ref_dir();
+
+            *tallies.entry(name.clone()).or_default().hits_mut() += 1.0;
+
+            return true;
+        }
+        Some(Kind::Lambertian(albedo)) => {
+            assert!(*albedo >= 0.0 && *albedo <= 1.0);
+
+            let effective_dist = (dist - bump_dist).max(MIN_POSITIVE);
+            *cell_rec.1.dist_travelled_mut() += effective_dist;
+            *cell_rec.1.weighted_dist_mut() += effective_dist * phot.weight();
+            phot.ray_mut().travel(effective_dist);
+
+            let facing = if phot.ray().dir().dot(&norm) > 0.0 {
+                Unit::new_unchecked(-norm.into_inner())
+            } else {
+                norm
+            };
+            let mut diffuse = Ray::new(*phot.ray().pos(), facing);
+            diffuse.rotate(
+                rng.gen_range(0.0_f64, 1.0).sqrt().asin(),
+                rng.gen_range(0.0, 2.0 * PI),
+            );
+            *phot.ray_mut().dir_mut() = *diffuse.dir();
+
+            let tally = tallies.entry(name.clone()).or_default();
+            *tally.hits_mut() += 1.0;
+            *tally.weight_mut() += (1.0 - albedo) * phot.weight();
+            *phot.weight_mut() *= albedo;
+
+            return *phot.weight() > 0.0;
+        }
+        Some(Kind::Refractive) | None => {}
+    }
+
     let next_mat = if inside {
         inter.out_mat()
     } else {
         inter.in_mat()
     };
-    let next_env = mats
+    let next_mat = surfs
+        .mats
         .map()
         .get(next_mat)
-        .expect("Invalid material name")
-        .optics()
-        .env(*phot.wavelength());
+        .expect("Invalid material name");
+    let next_env = next_mat.optics().env(*phot.wavelength());
 
     let n_curr = *env.ref_index();
     let n_next = *next_env.ref_index();
 
-    let crossing = Crossing::new(phot.ray().dir(), &norm, n_curr, n_next);
+    let (reflected, dir) = cross(rng, phot.ray().dir(), &norm, inter.rough(), n_curr, n_next);
 
-    if rng.gen_range(0.0, 1.0) <= *crossing.ref_prob() {
+    if reflected {
         let effective_dist = (dist - bump_dist).max(MIN_POSITIVE);
         *cell_rec.1.dist_travelled_mut() += effective_dist;
+        *cell_rec.1.weighted_dist_mut() += effective_dist * phot.weight();
         phot.ray_mut().travel(effective_dist);
-        *phot.ray_mut().dir_mut() = *crossing.ref_dir();
+        *phot.ray_mut().dir_mut() = dir;
     } else {
         let effective_dist = dist + bump_dist;
         *cell_rec.1.dist_travelled_mut() += effective_dist;
+        *cell_rec.1.weighted_dist_mut() += effective_dist * phot.weight();
         phot.ray_mut().travel(effective_dist);
-        *phot.ray_mut().dir_mut() = crossing
-            .trans_dir()
-            .expect("Failed to determine transmission direction.");
+        *phot.ray_mut().dir_mut() = dir;
 
+        *mat = next_mat;
         *env = next_env;
     }
+
+    true
+}
+
+/// Determine if a photon is reflected at an interface, and its subsequent direction of travel.
+/// Rough interfaces sample microfacet normals until the outgoing direction lies on the physical side of the surface.
+#[inline]
+#[must_use]
+fn cross(
+    rng: &mut ThreadRng,
+    inc: &Unit<Vector3<f64>>,
+    norm: &Unit<Vector3<f64>>,
+    rough: &Option<Microfacet>,
+    n_curr: f64,
+    n_next: f64,
+) -> (bool, Unit<Vector3<f64>>) {
+    let facing = if inc.dot(norm) > 0.0 {
+        Unit::new_unchecked(-norm.into_inner())
+    } else {
+        *norm
+    };
+
+    if let Some(rough) = rough {
+        for _ in 0..MAX_FACET_SAMPLES {
+            let facet = rough.sample_norm(rng, &facing);
+            if inc.dot(&facet) >= 0.0 {
+                continue;
+            }
+
+            let crossing = Crossing::new(inc, &facet, n_curr, n_next);
+            if rng.gen_range(0.0, 1.0) <= *crossing.ref_prob() {
+                if crossing.ref_dir().dot(&facing) > 0.0 {
+                    return (true, *crossing.ref_dir());
+                }
+            } else if let Some(trans_dir) = crossing.trans_dir() {
+                if trans_dir.dot(&facing) < 0.0 {
+                    return (false, *trans_dir);
+                }
+            }
+        }
+    }
+
+    let crossing = Crossing::new(inc, norm, n_curr, n_next);
+    if rng.gen_range(0.0, 1.0) <= *crossing.ref_prob() {
+        (true, *crossing.ref_dir())
+    } else {
+        (
+            false,
+            crossing
+                .trans_dir()
+                .expect("Failed to determine transmission direction."),
+        )
+    }
 }
@@ -17,6 +17,8 @@ pub struct Record {
     shifts: f64,
     /// Total distance travelled by photons.
     dist_travelled: f64,
+    /// Total weighted distance travelled by photons.
+    weighted_dist: f64,
 }
 
 impl Record {
@@ -25,6 +27,7 @@ impl Record {
     access!(absorptions, absorptions_mut, f64);
     access!(shifts, shifts_mut, f64);
     access!(dist_travelled, dist_travelled_mut, f64);
+    access!(weighted_dist, weighted_dist_mut, f64);
 }
 
 impl Default for Record {
@@ -37,6 +40,7 @@ impl Default for Record {
             absorptions: 0.0,
             shifts: 0.0,
             dist_travelled: 0.0,
+            weighted_dist: 0.0,
         }
     }
 }
@@ -48,5 +52,6 @@ impl AddAssign<Self> for Record {
         self.absorptions += rhs.absorptions;
         self.shifts += rhs.shifts;
         self.dist_travelled += rhs.dist_travelled;
+        self.weighted_dist += rhs.weighted_dist;
     }
 }
@@ -0,0 +1,38 @@
+//! Interface tally structure.
+
+use crate::access;
+use attr::json;
+use std::ops::AddAssign;
+
+/// Tally structure implementation.
+/// Stores data about photon interactions with a single non-refractive interface.
+#[json]
+#[derive(Clone)]
+pub struct Tally {
+    /// Total number of photon hits.
+    hits: f64,
+    /// Total weight absorbed or detected.
+    weight: f64,
+}
+
+impl Tally {
+    access!(hits, hits_mut, f64);
+    access!(weight, weight_mut, f64);
+}
+
+impl Default for Tally {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            hits: 0.0,
+            weight: 0.0,
+        }
+    }
+}
+
+impl AddAssign<&Self> for Tally {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.hits += rhs.hits;
+        self.weight += rhs.weight;
+    }
+}
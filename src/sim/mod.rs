@@ -1,5 +1,11 @@
 //! Simulation module.
 
+pub mod fit;
+pub mod heat;
+pub mod kin;
 pub mod mcrt;
+pub mod pdt;
+pub mod ssa;
+pub mod steady;
 
 pub use self::mcrt::*;
@@ -0,0 +1,90 @@
+//! Photodynamic therapy simulation sub-module.
+
+use crate::{
+    chem::Network,
+    data::Table,
+    dom::{Name, Regular},
+    file::Kinetics,
+    sim::{
+        kin::{
+            diffusion, evolve, headings, heat, illuminate, metabolisms, mults, row, snapshot,
+            Chemistry,
+        },
+        mcrt, LightMap,
+    },
+    uni::Verse,
+};
+use log::info;
+use ndarray::Array3;
+use std::path::Path;
+
+/// Alternate light transport and chemistry so that light dose and photobleaching are self-consistent.
+/// The light field is re-simulated, using the current species concentrations, at each of the cycle times.
+/// Returns the final light map, and the mean species concentrations at the start and each of the output times.
+#[inline]
+#[must_use]
+pub fn run(
+    name: &Name,
+    num_phot: u64,
+    verse: &Verse,
+    grid: &mut Regular,
+    kin: &Kinetics,
+    out_dir: &Path,
+) -> (LightMap, Table) {
+    let net = Network::new(verse.reacts(), verse.specs());
+    let metabs = metabolisms(verse);
+    let mut diff = diffusion(verse, grid, kin);
+    if let Some(temp) = kin.temp() {
+        heat(grid, *temp);
+    }
+
+    let mults = mults(verse, grid);
+    let chem = Chemistry::new(&net, &metabs, &mults, *kin.tol());
+    let mut dts: Array3<f64> = Array3::zeros(grid.res());
+
+    let times = kin.times();
+    let snaps = kin
+        .snaps()
+        .as_ref()
+        .map_or(&[][..], |snaps| snaps.as_slice());
+    let cycles = kin
+        .cycles()
+        .as_ref()
+        .map_or(&[][..], |cycles| cycles.as_slice());
+
+    let mut table = Table::new(headings(verse, &diff));
+    table.push(row(0.0, grid, &diff));
+
+    let mut targets: Vec<f64> = times.iter().chain(snaps).chain(cycles).cloned().collect();
+    targets.sort_by(|a, b| a.partial_cmp(b).expect("Invalid output time."));
+    targets.dedup();
+
+    if snaps.contains(&0.0) {
+        snapshot(0.0, verse, grid, out_dir, kin);
+    }
+
+    info!("Light cycle at 0s");
+    let mut light_map = mcrt::run(name, num_phot, verse, grid);
+    illuminate(grid, &light_map);
+
+    let mut time = 0.0;
+    for t in targets {
+        assert!(t >= time);
+        evolve(&chem, &mut diff, grid, &mut dts, t - time);
+
+        time = t;
+        if times.contains(&time) {
+            table.push(row(time, grid, &diff));
+        }
+        if snaps.contains(&time) {
+            snapshot(time, verse, grid, out_dir, kin);
+        }
+        if cycles.contains(&time) {
+            info!("Light cycle at {}s", time);
+            light_map = mcrt::run(name, num_phot, verse, grid);
+            illuminate(grid, &light_map);
+        }
+    }
+
+    (light_map, table)
+}
@@ -0,0 +1,106 @@
+//! Stochastic kinetics simulation sub-module.
+
+use crate::{
+    chem::{Local, Network, BODY_TEMP},
+    data::Table,
+    file::Stochastic,
+    math::integrate,
+    uni::Verse,
+    util::bar,
+};
+use ndarray::Array1;
+use rand::thread_rng;
+
+/// Error tolerance of the deterministic comparison integration.
+const DET_TOL: f64 = 1.0e-6;
+
+/// Simulate an ensemble of well-mixed cells of a material with discrete molecule counts.
+/// Returns the ensemble mean and standard deviation, and the deterministic prediction, of each species concentration at the start and each of the output times.
+#[inline]
+#[must_use]
+pub fn run(verse: &Verse, ssa: &Stochastic) -> Table {
+    let net = Network::new(verse.reacts(), verse.specs());
+    let mat = verse
+        .mats()
+        .map()
+        .get(ssa.mat())
+        .expect("Invalid material name.");
+    let mult = mat.reaction_multiplier().unwrap_or(0.0);
+    let local = Local::new(
+        ssa.fluence_rate().unwrap_or(0.0),
+        0.0,
+        ssa.temp().unwrap_or(BODY_TEMP),
+    );
+    let init = mat.init_state().as_ref().map_or_else(
+        || Array1::zeros(verse.specs().map().len()),
+        |state| state.build(verse.specs()).concs().clone(),
+    );
+
+    let size = *ssa.size();
+    let mut times = vec![0.0];
+    times.extend(ssa.times());
+
+    let pb = bar("Ensemble", *ssa.runs() as u64);
+    let mut rng = thread_rng();
+    let mut samples = vec![Vec::with_capacity(*ssa.runs()); times.len()];
+    for _ in 0..*ssa.runs() {
+        pb.inc(1);
+
+        let mut counts = (&init * size).mapv(f64::round);
+        let mut time = 0.0;
+        for (t, sample) in times.iter().zip(samples.iter_mut()) {
+            assert!(*t >= time);
+            // Scaling every rate by the multiplier is equivalent to scaling the elapsed time.
+            ssa.method()
+                .evolve(&mut rng, &net, &mut counts, size, &local, (t - time) * mult);
+            time = *t;
+            sample.push(&counts / size);
+        }
+    }
+    pb.finish_with_message("Ensemble complete.");
+
+    let mut headings = vec!["time".to_string()];
+    for name in verse.specs().map().keys() {
+        headings.push(format!("{}_mean", name));
+        headings.push(format!("{}_std", name));
+        headings.push(format!("{}_det", name));
+    }
+    let mut table = Table::new(headings);
+
+    let mut det = init;
+    let mut dt = 0.0;
+    let mut time = 0.0;
+    for (t, sample) in times.iter().zip(&samples) {
+        integrate(
+            |concs| net.deriv(concs, mult, &local),
+            |concs| net.jacobian(concs, mult, &local),
+            &mut det,
+            t - time,
+            &mut dt,
+            DET_TOL,
+        );
+        time = *t;
+
+        let num = sample.len() as f64;
+        let mean = sample
+            .iter()
+            .fold(Array1::<f64>::zeros(det.len()), |sum, concs| sum + concs)
+            / num;
+        let var = sample
+            .iter()
+            .fold(Array1::<f64>::zeros(det.len()), |sum, concs| {
+                sum + (concs - &mean).mapv(|x| x * x)
+            })
+            / (num - 1.0).max(1.0);
+
+        let mut row = vec![*t];
+        for ((m, v), d) in mean.iter().zip(var.iter()).zip(det.iter()) {
+            row.push(*m);
+            row.push(v.sqrt());
+            row.push(*d);
+        }
+        table.push(row);
+    }
+
+    table
+}
@@ -0,0 +1,214 @@
+//! Steady-state kinetics simulation sub-module.
+
+use crate::{
+    chem::{Diffusion, Network},
+    data::Table,
+    dom::{Name, Regular},
+    file::{Kinetics, Steady},
+    math::{gmres, norm},
+    sim::kin::{diffusion, heat, metabolisms, mults},
+    uni::Verse,
+};
+use log::{info, warn};
+use ndarray::{Array1, Array3, Array4, Axis, Ix4, Zip};
+use std::collections::BTreeMap;
+
+/// Default Krylov subspace dimension.
+const KRYLOV: usize = 30;
+
+/// Maximum number of linear iterations of each pseudo-time step, as a multiple of the Krylov subspace dimension.
+const RESTARTS: usize = 10;
+
+/// Relative residual tolerance of each linear solve.
+const LINEAR_TOL: f64 = 1.0e-3;
+
+/// Largest pseudo-time step. [s]
+const MAX_STEP: f64 = 1.0e12;
+
+/// Solve for the steady-state concentration field by pseudo-transient continuation.
+/// Each pseudo-time step is a Jacobian-free Newton-Krylov solve, preconditioned by the Jacobian diagonal,
+/// and the step grows as the residual falls.
+/// Time dependent supplies, creams and plasma, are excluded.
+/// Returns the residual norm, pseudo-time step and number of linear iterations of each step.
+#[inline]
+#[must_use]
+pub fn run(verse: &Verse, grid: &mut Regular, kin: &Kinetics, steady: &Steady) -> Table {
+    let net = Network::new(verse.reacts(), verse.specs());
+    let metabs = metabolisms(verse);
+    let diff = diffusion(verse, grid, kin);
+    if let Some(temp) = kin.temp() {
+        heat(grid, *temp);
+    }
+    let mults = mults(verse, grid);
+    let krylov = steady.krylov().unwrap_or(KRYLOV);
+
+    let mut table = Table::new(vec![
+        "step".to_string(),
+        "residual".to_string(),
+        "pseudo_step".to_string(),
+        "linear_iters".to_string(),
+    ]);
+
+    let mut concs = grid.concs();
+    diff.hold(&mut concs);
+    let shape = concs.raw_dim();
+
+    let concs = {
+        let grid: &Regular = grid;
+        let res = |concs: &Array4<f64>| {
+            let mut res = residual(&net, &metabs, &diff, grid, &mults, concs);
+            for (index, _conc) in diff.held() {
+                *res.get_mut(*index).expect("Invalid held index.") = 0.0;
+            }
+            res
+        };
+
+        let mut f = res(&concs);
+        let f_0 = norm(&flatten(&f));
+        let mut f_norm = f_0;
+        let mut tau = *steady.init_step();
+        table.push(vec![0.0, f_norm, tau, 0.0]);
+        info!("Initial residual: {}", f_norm);
+
+        let mut converged = f_norm <= 0.0;
+        for step in 1..=*steady.max_steps() {
+            if converged {
+                break;
+            }
+
+            let mut diag = diff.diagonal() + reaction_diagonal(&net, &metabs, grid, &mults, &concs);
+            diag.mapv_inplace(|d| (1.0 / tau) + d.max(0.0));
+            for (index, _conc) in diff.held() {
+                *diag.get_mut(*index).expect("Invalid held index.") = 1.0 / tau;
+            }
+            let precon = flatten(&diag);
+
+            let c_norm = norm(&flatten(&concs));
+            let f_flat = flatten(&f);
+            let op = |y: &Array1<f64>| {
+                let v = y / &precon;
+                let v_norm = norm(&v);
+                if v_norm <= 0.0 {
+                    return v;
+                }
+
+                let eps = std::f64::EPSILON.sqrt() * (1.0 + c_norm) / v_norm;
+                let perturbed = &concs + &(unflatten(&v, shape) * eps);
+                let jv = (flatten(&res(&perturbed)) - &f_flat) / eps;
+                (&v / tau) - jv
+            };
+
+            let mut y = Array1::zeros(f_flat.len());
+            let (iters, _lin_res) =
+                gmres(op, &f_flat, &mut y, krylov, LINEAR_TOL, krylov * RESTARTS);
+
+            let mut next = &concs + &unflatten(&(y / &precon), shape);
+            next.mapv_inplace(|c| c.max(0.0));
+            diff.hold(&mut next);
+            let f_next = res(&next);
+            let f_next_norm = norm(&flatten(&f_next));
+
+            concs = next;
+            f = f_next;
+            tau = if f_next_norm > 0.0 {
+                (tau * f_norm / f_next_norm).min(MAX_STEP)
+            } else {
+                MAX_STEP
+            };
+            f_norm = f_next_norm;
+
+            table.push(vec![step as f64, f_norm, tau, iters as f64]);
+            info!(
+                "Step {}: residual {} ({} relative)\tpseudo-step {}s\tlinear iterations {}",
+                step,
+                f_norm,
+                f_norm / f_0,
+                tau,
+                iters
+            );
+
+            converged = f_norm <= steady.tol() * f_0;
+        }
+
+        if !converged {
+            warn!("Steady-state solver did not converge.");
+        }
+
+        concs
+    };
+    grid.set_concs(&concs);
+
+    table
+}
+
+/// Calculate the rate of change of the concentration field due to diffusion, reactions, metabolic consumption and species sources.
+#[inline]
+#[must_use]
+pub fn residual(
+    net: &Network,
+    metabs: &BTreeMap<Name, Network>,
+    diff: &Diffusion,
+    grid: &Regular,
+    mults: &Array3<f64>,
+    concs: &Array4<f64>,
+) -> Array4<f64> {
+    let mut rate = diff.rate(concs);
+
+    Zip::from(rate.lanes_mut(Axis(3)))
+        .and(concs.lanes(Axis(3)))
+        .and(grid.cells())
+        .and(mults)
+        .par_apply(|mut rate, concs, cell, mult| {
+            let concs = concs.to_owned();
+            let local = cell.state().local();
+            rate += cell.state().sources();
+            if *mult > 0.0 {
+                rate += &net.deriv(&concs, *mult, local);
+            }
+            if let Some(metab) = metabs.get(cell.mat()) {
+                rate += &metab.deriv(&concs, 1.0, local);
+            }
+        });
+
+    rate
+}
+
+/// Calculate the diagonal of the negated reaction and metabolic consumption Jacobian. [s^-1]
+#[inline]
+#[must_use]
+pub fn reaction_diagonal(
+    net: &Network,
+    metabs: &BTreeMap<Name, Network>,
+    grid: &Regular,
+    mults: &Array3<f64>,
+    concs: &Array4<f64>,
+) -> Array4<f64> {
+    let mut diag = Array4::zeros(concs.raw_dim());
+
+    Zip::from(diag.lanes_mut(Axis(3)))
+        .and(concs.lanes(Axis(3)))
+        .and(grid.cells())
+        .and(mults)
+        .par_apply(|mut diag, concs, cell, mult| {
+            let concs = concs.to_owned();
+            let local = cell.state().local();
+            if *mult > 0.0 {
+                diag -= &net.jacobian(&concs, *mult, local).diag();
+            }
+            if let Some(metab) = metabs.get(cell.mat()) {
+                diag -= &metab.jacobian(&concs, 1.0, local).diag();
+            }
+        });
+
+    diag
+}
+
+/// Flatten a concentration field into a vector.
+fn flatten(field: &Array4<f64>) -> Array1<f64> {
+    field.iter().cloned().collect()
+}
+
+/// Reshape a vector into a concentration field.
+fn unflatten(vec: &Array1<f64>, shape: Ix4) -> Array4<f64> {
+    Array4::from_shape_vec(shape, vec.to_vec()).expect("Invalid field shape.")
+}
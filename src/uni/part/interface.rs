@@ -1,8 +1,11 @@
 //! Interface implementation.
 
-use crate::{access, dom::Name};
+use crate::{access, dom::Name, phys::Microfacet, uni::Kind};
 use attr::json;
-use std::fmt::{Display, Formatter, Result};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+};
 
 /// Material interface structure.
 #[json]
@@ -13,21 +16,45 @@ pub struct Interface {
     in_mat: Name,
     /// Outside material.
     out_mat: Name,
+    /// Optional surface roughness, optically smooth otherwise.
+    rough: Option<Microfacet>,
+    /// Optional optical behaviour, refractive otherwise.
+    kind: Option<Kind>,
+    /// Optional species partition coefficients, the equilibrium ratio of inside to outside concentration.
+    partition: Option<BTreeMap<Name, f64>>,
+    /// Optional species permeabilities of the interface itself. [m s^-1]
+    perm: Option<BTreeMap<Name, f64>>,
 }
 
 impl<'a> Interface {
     access!(surf, Name);
     access!(in_mat, Name);
     access!(out_mat, Name);
+    access!(rough, Option<Microfacet>);
+    access!(kind, Option<Kind>);
+    access!(partition, Option<BTreeMap<Name, f64>>);
+    access!(perm, Option<BTreeMap<Name, f64>>);
 
     /// Construct a new instance.
     #[inline]
     #[must_use]
-    pub fn new(surf: Name, in_mat: Name, out_mat: Name) -> Self {
+    pub fn new(
+        surf: Name,
+        in_mat: Name,
+        out_mat: Name,
+        rough: Option<Microfacet>,
+        kind: Option<Kind>,
+        partition: Option<BTreeMap<Name, f64>>,
+        perm: Option<BTreeMap<Name, f64>>,
+    ) -> Self {
         Self {
             surf,
             in_mat,
             out_mat,
+            rough,
+            kind,
+            partition,
+            perm,
         }
     }
 }
@@ -38,6 +65,30 @@ impl Display for Interface {
             fmt,
             "surf: {}\t{} :| {}",
             self.surf, self.in_mat, self.out_mat
-        )
+        )?;
+
+        if let Some(kind) = &self.kind {
+            write!(fmt, "\t{}", kind)?;
+        }
+
+        if let Some(rough) = &self.rough {
+            write!(fmt, "\trough: {}", rough)?;
+        }
+
+        if let Some(partition) = &self.partition {
+            write!(fmt, "\tPartition:")?;
+            for (name, k) in partition {
+                write!(fmt, " {} ({})", name, k)?;
+            }
+        }
+
+        if let Some(perm) = &self.perm {
+            write!(fmt, "\tPermeability:")?;
+            for (name, p) in perm {
+                write!(fmt, " {} ({} m s^-1)", name, p)?;
+            }
+        }
+
+        Ok(())
     }
 }
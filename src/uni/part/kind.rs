@@ -0,0 +1,31 @@
+//! Interface kind implementation.
+
+use attr::json;
+use std::fmt::{Display, Formatter, Result};
+
+/// Optical behaviour of an interface.
+#[json]
+pub enum Kind {
+    /// Refractive boundary between the inside and outside materials.
+    Refractive,
+    /// Perfect specular reflector.
+    Mirror,
+    /// Perfect absorber.
+    Absorber,
+    /// Lambertian diffuse reflector. (albedo)
+    Lambertian(f64),
+    /// Counting detector, absorbing incident photons.
+    Detector,
+}
+
+impl Display for Kind {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Self::Refractive => write!(fmt, "Refractive"),
+            Self::Mirror => write!(fmt, "Mirror"),
+            Self::Absorber => write!(fmt, "Absorber"),
+            Self::Lambertian(albedo) => write!(fmt, "Lambertian ({})", albedo),
+            Self::Detector => write!(fmt, "Detector"),
+        }
+    }
+}
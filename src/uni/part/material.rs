@@ -1,8 +1,19 @@
 //! Material implementation.
 
-use crate::{access, file::State as FileState, phys::Optics};
+use crate::{
+    access,
+    chem::{Boundary, Consumption, Perfusion, Species},
+    dom::{index_of_name, Name, Set},
+    file::State as FileState,
+    math::Formula,
+    phys::{Coagulation, Fibres, Optics, Thermal},
+};
 use attr::json;
-use std::fmt::{Display, Formatter, Result};
+use ndarray::Array1;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+};
 
 /// Material physical properties.
 #[json]
@@ -15,6 +26,26 @@ pub struct Material {
     reaction_multiplier: Option<f64>,
     /// Initial state.
     init_state: Option<FileState>,
+    /// Optional aligned fibre scattering.
+    fibres: Option<Fibres>,
+    /// Optional species absorption, as extinction coefficients per unit concentration. [m^-1]
+    chromophores: Option<BTreeMap<Name, Formula>>,
+    /// Optional species boundary conditions.
+    /// Fixed concentrations are held within the material, fluxes enter neighbouring materials, and closed species cannot cross.
+    bounds: Option<BTreeMap<Name, Boundary>>,
+    /// Optional blood perfusion supplying species.
+    perfusion: Option<Perfusion>,
+    /// Optional metabolic consumption of species.
+    consumption: Option<BTreeMap<Name, Consumption>>,
+    /// Optional plasma exchange rate of vascular materials. [s^-1]
+    vascular: Option<f64>,
+    /// Optional thermal properties.
+    thermal: Option<Thermal>,
+    /// Optional Arrhenius thermal damage and coagulated optical properties.
+    coagulation: Option<Coagulation>,
+    /// Species indices of the chromophores, resolved once the species set is known.
+    #[serde(skip)]
+    chromo_indices: Vec<usize>,
 }
 
 impl Material {
@@ -22,6 +53,14 @@ impl Material {
     access!(visc, Option<f64>);
     access!(reaction_multiplier, Option<f64>);
     access!(init_state, Option<FileState>);
+    access!(fibres, Option<Fibres>);
+    access!(chromophores, Option<BTreeMap<Name, Formula>>);
+    access!(bounds, Option<BTreeMap<Name, Boundary>>);
+    access!(perfusion, Option<Perfusion>);
+    access!(consumption, Option<BTreeMap<Name, Consumption>>);
+    access!(vascular, Option<f64>);
+    access!(thermal, Option<Thermal>);
+    access!(coagulation, Option<Coagulation>);
 
     /// Construct a new instance.
     #[inline]
@@ -31,14 +70,85 @@ impl Material {
         visc: Option<f64>,
         reaction_multiplier: Option<f64>,
         init_state: Option<FileState>,
+        fibres: Option<Fibres>,
+        chromophores: Option<BTreeMap<Name, Formula>>,
+        bounds: Option<BTreeMap<Name, Boundary>>,
+        perfusion: Option<Perfusion>,
+        consumption: Option<BTreeMap<Name, Consumption>>,
+        vascular: Option<f64>,
+        thermal: Option<Thermal>,
+        coagulation: Option<Coagulation>,
     ) -> Self {
         Self {
             visc,
             reaction_multiplier,
             optics,
             init_state,
+            fibres,
+            chromophores,
+            bounds,
+            perfusion,
+            consumption,
+            vascular,
+            thermal,
+            coagulation,
+            chromo_indices: Vec::new(),
         }
     }
+
+    /// Resolve the species indices of the chromophores.
+    /// Panics if a chromophore species is not within the species set.
+    #[inline]
+    pub fn resolve(&mut self, specs: &Set<Species>) {
+        self.chromo_indices = self
+            .chromophores
+            .as_ref()
+            .map_or_else(Vec::new, |chromophores| {
+                chromophores
+                    .keys()
+                    .map(|name| {
+                        assert!(
+                            specs.map().contains_key(name),
+                            "Chromophore species {} is not used by any reaction.",
+                            name
+                        );
+                        index_of_name(name, specs)
+                    })
+                    .collect()
+            });
+    }
+
+    /// Get the coagulated optical properties of a cell with the given thermal damage integral.
+    /// Coagulated optics, if defined, apply once the damage integral reaches unity.
+    #[inline]
+    #[must_use]
+    pub fn coagulated(&self, damage: f64) -> Option<&Optics> {
+        self.coagulation
+            .as_ref()
+            .and_then(|coag| coag.optics().as_ref())
+            .filter(|_optics| damage >= 1.0)
+    }
+
+    /// Calculate the absorption coefficient due to the species concentrations at a given wavelength. [m^-1]
+    /// Chromophore species indices must have been resolved.
+    #[inline]
+    #[must_use]
+    pub fn chromo_abs(&self, w: f64, concs: &Array1<f64>) -> f64 {
+        self.chromophores.as_ref().map_or(0.0, |chromophores| {
+            assert!(
+                chromophores.len() == self.chromo_indices.len(),
+                "Unresolved chromophore species."
+            );
+
+            chromophores
+                .values()
+                .zip(&self.chromo_indices)
+                .map(|(ext, index)| {
+                    ext.y(w) * concs.get(*index).expect("Invalid species index.").max(0.0)
+                })
+                .sum()
+        })
+    }
 }
 
 impl Display for Material {
@@ -59,6 +169,47 @@ impl Display for Material {
             write!(fmt, "Inert.")?;
         }
 
+        if let Some(fibres) = &self.fibres {
+            write!(fmt, "\t{}", fibres)?;
+        }
+
+        if let Some(chromophores) = &self.chromophores {
+            write!(fmt, "\tChromophores:")?;
+            for name in chromophores.keys() {
+                write!(fmt, " {}", name)?;
+            }
+        }
+
+        if let Some(bounds) = &self.bounds {
+            write!(fmt, "\tBounds:")?;
+            for (name, bound) in bounds {
+                write!(fmt, " {}: {}", name, bound)?;
+            }
+        }
+
+        if let Some(perfusion) = &self.perfusion {
+            write!(fmt, "\t{}", perfusion)?;
+        }
+
+        if let Some(consumption) = &self.consumption {
+            write!(fmt, "\tConsumption:")?;
+            for (name, cons) in consumption {
+                write!(fmt, " {} ({})", name, cons)?;
+            }
+        }
+
+        if let Some(rate) = self.vascular {
+            write!(fmt, "\tVascular ({} s^-1)", rate)?;
+        }
+
+        if let Some(thermal) = &self.thermal {
+            write!(fmt, "\tThermal ({})", thermal)?;
+        }
+
+        if let Some(coagulation) = &self.coagulation {
+            write!(fmt, "\t{}", coagulation)?;
+        }
+
         Ok(())
     }
 }
@@ -1,8 +1,9 @@
 //! Part sub-module.
 
 pub mod interface;
+pub mod kind;
 pub mod light;
 pub mod material;
 pub mod state;
 
-pub use self::{interface::*, light::*, material::*, state::*};
+pub use self::{interface::*, kind::*, light::*, material::*, state::*};
@@ -1,6 +1,6 @@
 //! State implementation.
 
-use crate::access;
+use crate::{access, chem::Local};
 use ndarray::Array1;
 
 /// Local species state.
@@ -9,17 +9,28 @@ pub struct State {
     concs: Array1<f64>,
     /// Species sources.
     sources: Array1<f64>,
+    /// Local reaction conditions.
+    local: Local,
+    /// Arrhenius thermal damage integral.
+    damage: f64,
 }
 
 impl State {
     access!(concs, concs_mut, Array1<f64>);
     access!(sources, Array1<f64>);
+    access!(local, local_mut, Local);
+    access!(damage, damage_mut, f64);
 
     /// Construct a new instance.
     #[inline]
     #[must_use]
     pub fn new(concs: Array1<f64>, sources: Array1<f64>) -> Self {
-        Self { concs, sources }
+        Self {
+            concs,
+            sources,
+            local: Local::default(),
+            damage: 0.0,
+        }
     }
 
     /// Construct a new empty instance.